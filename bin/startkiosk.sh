@@ -0,0 +1,46 @@
+#!/usr/bin/env bash
+
+# Launches the application of a kiosk session profile, provided by the session manager:
+#  - KIOSK_APPLICATION: the command of the application
+#  - KIOSK_WINDOW_MANAGER: the command of an optional minimal window manager
+#  - KIOSK_ON_EXIT: restart (the application is restarted when it exits) or logout (the session ends)
+
+if [ -r /etc/profile ]; then
+  . /etc/profile
+fi
+if [ -r ~/.profile ]; then
+  . ~/.profile
+fi
+
+# the locale requested at login (WEBX_LOCALE) takes precedence over the default of the host
+if [ -z "$WEBX_LOCALE" ] && [ -r /etc/default/locale ]; then
+  . /etc/default/locale
+  export LANG LANGUAGE
+fi
+
+if [ -z "$KIOSK_APPLICATION" ]; then
+  echo "No kiosk application specified" >&2
+  exit 1
+fi
+
+if [ -n "$KIOSK_WINDOW_MANAGER" ]; then
+  eval "$KIOSK_WINDOW_MANAGER" &
+  WINDOW_MANAGER_PID=$!
+fi
+
+if [ "$KIOSK_ON_EXIT" = "restart" ]; then
+  while true; do
+    eval "$KIOSK_APPLICATION"
+    echo "Kiosk application exited with status $?, restarting" >&2
+    sleep 1
+  done
+fi
+
+eval "$KIOSK_APPLICATION"
+STATUS=$?
+
+if [ -n "$WINDOW_MANAGER_PID" ]; then
+  kill "$WINDOW_MANAGER_PID" 2>/dev/null
+fi
+
+exit $STATUS
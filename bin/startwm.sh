@@ -59,7 +59,8 @@ get_xdg_session_startupcmd()
 #start the window manager
 wm_start()
 {
-  if [ -r /etc/default/locale ]; then
+  # the locale requested at login (WEBX_LOCALE) takes precedence over the default of the host
+  if [ -z "$WEBX_LOCALE" ] && [ -r /etc/default/locale ]; then
     . /etc/default/locale
     export LANG LANGUAGE
   fi
@@ -86,7 +87,12 @@ wm_start()
     fi
 
     #. /etc/X11/Xsession
-    startxfce4
+    # the session manager provides STARTUP when a desktop has been chosen at login
+    if [ -n "$STARTUP" ]; then
+      eval "$STARTUP"
+    else
+      startxfce4
+    fi
 
     post_start
     exit 0
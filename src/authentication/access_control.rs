@@ -0,0 +1,141 @@
+use chrono::NaiveDateTime;
+
+use crate::common::{AccessSettings, Account, ApplicationError, LoginWindowPolicy};
+
+use super::LoginWindow;
+
+/// The `AccessControl` struct determines whether an account is permitted to start a desktop session
+/// using the configured allow and deny lists of users and Unix groups, and the login windows.
+pub struct AccessControl {
+    settings: AccessSettings,
+    login_windows: Vec<LoginWindow>,
+}
+
+impl AccessControl {
+    /// Creates a new `AccessControl` instance.
+    ///
+    /// # Arguments
+    /// * `settings` - The access control settings.
+    ///
+    /// # Returns
+    /// A new `AccessControl` instance.
+    pub fn new(settings: AccessSettings) -> Self {
+        let login_windows = settings.login_windows()
+            .iter()
+            .filter_map(|login_window| {
+                let parsed = LoginWindow::from_settings(login_window);
+                if parsed.is_none() {
+                    error!("Ignoring invalid login window {}", login_window.name());
+                }
+                parsed
+            })
+            .collect();
+
+        Self {
+            settings,
+            login_windows,
+        }
+    }
+
+    /// Returns whether running sessions are terminated when their user is no longer permitted by the login windows.
+    pub fn terminate_outside_windows(&self) -> bool {
+        self.settings.terminate_outside_windows() && !self.login_windows.is_empty()
+    }
+
+    /// Returns the number of minutes before termination that a session user is warned.
+    pub fn termination_warning(&self) -> u64 {
+        self.settings.termination_warning()
+    }
+
+    /// Checks whether an account is permitted to start a session, by the user and group rules and the login windows.
+    ///
+    /// # Arguments
+    /// * `account` - The account to check.
+    ///
+    /// # Returns
+    /// A `Result` indicating the account is permitted or an `ApplicationError` explaining why it is not.
+    pub fn check(&self, account: &Account) -> Result<(), ApplicationError> {
+        self.check_rules(account)?;
+        self.check_login_windows(account, &chrono::Local::now().naive_local())
+    }
+
+    /// Checks whether an account is permitted by the user and group rules, regardless of the login windows.
+    ///
+    /// Deny rules take precedence over allow rules. When allowed users or groups are configured,
+    /// the account must be one of the allowed users or a member of one of the allowed groups.
+    ///
+    /// # Arguments
+    /// * `account` - The account to check.
+    ///
+    /// # Returns
+    /// A `Result` indicating the account is permitted or an `ApplicationError` explaining why it is not.
+    pub fn check_rules(&self, account: &Account) -> Result<(), ApplicationError> {
+        let username = account.username();
+
+        if account.uid() == 0 && self.settings.deny_root() {
+            return Err(ApplicationError::authentication("The root user is not permitted to start a session"));
+        }
+
+        if let Some(minimum_uid) = self.settings.minimum_uid() {
+            if account.uid() < minimum_uid {
+                return Err(ApplicationError::authentication(format!("User {} is a system account (uid {} is below {})", username, account.uid(), minimum_uid)));
+            }
+        }
+
+        if self.settings.denied_users().iter().any(|denied_user| denied_user == username) {
+            return Err(ApplicationError::authentication(format!("User {} is denied access", username)));
+        }
+
+        if let Some(group) = self.settings.denied_groups().iter().find(|group| account.is_member_of(group)) {
+            return Err(ApplicationError::authentication(format!("User {} is denied access as a member of group {}", username, group)));
+        }
+
+        let allowed_users = self.settings.allowed_users();
+        let allowed_groups = self.settings.allowed_groups();
+        let is_allowed = (allowed_users.is_empty() && allowed_groups.is_empty()) ||
+            allowed_users.iter().any(|allowed_user| allowed_user == username) ||
+            allowed_groups.iter().any(|group| account.is_member_of(group));
+        if !is_allowed {
+            return Err(ApplicationError::authentication(format!("User {} is not an allowed user or a member of an allowed group", username)));
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether an account is permitted to be logged in at a given time by the login windows.
+    ///
+    /// An account that is a member of the groups of restricting windows must be within one of them.
+    /// During an open reserving window, the account must be a member of its groups.
+    /// Exempt accounts are not subject to the window.
+    ///
+    /// # Arguments
+    /// * `account` - The account to check.
+    /// * `at` - The local date and time.
+    ///
+    /// # Returns
+    /// A `Result` indicating the account is permitted or an `ApplicationError` explaining why it is not.
+    pub fn check_login_windows(&self, account: &Account, at: &NaiveDateTime) -> Result<(), ApplicationError> {
+        let username = account.username();
+        let applicable_windows: Vec<&LoginWindow> = self.login_windows
+            .iter()
+            .filter(|login_window| !login_window.is_exempt(account))
+            .collect();
+
+        if let Some(reservation) = applicable_windows
+            .iter()
+            .find(|login_window| login_window.policy() == LoginWindowPolicy::Reserve && login_window.is_open(at) && !login_window.is_member(account)) {
+            return Err(ApplicationError::authentication(format!("The host is reserved for the login window {}", reservation.name())));
+        }
+
+        let restrictions: Vec<&&LoginWindow> = applicable_windows
+            .iter()
+            .filter(|login_window| login_window.policy() == LoginWindowPolicy::Restrict && login_window.is_member(account))
+            .collect();
+        if !restrictions.is_empty() && !restrictions.iter().any(|login_window| login_window.is_open(at)) {
+            let names: Vec<&str> = restrictions.iter().map(|login_window| login_window.name()).collect();
+            return Err(ApplicationError::authentication(format!("User {} may only log in during the login windows {}", username, names.join(", "))));
+        }
+
+        Ok(())
+    }
+}
@@ -5,6 +5,24 @@ use pam_client::conv_mock::Conversation;
 use crate::authentication::Credentials;
 use crate::common::{ApplicationError};
 
+/// The `Authentication` struct holds the PAM context of a user whose credentials and account have been verified,
+/// so that the PAM session modules only run once all the login checks have passed.
+pub struct Authentication {
+    context: Context<Conversation>,
+}
+
+impl Authentication {
+    /// Opens the PAM session of the authenticated user.
+    ///
+    /// # Returns
+    /// A `Result` containing an `EnvList` of environment variables if the session is opened,
+    /// or an `ApplicationError` if the session cannot be opened.
+    pub fn open_session(mut self) -> Result<EnvList, ApplicationError> {
+        let session = self.context.open_session(Flag::NONE)?;
+        Ok(session.envlist())
+    }
+}
+
 /// The `Authenticator` struct provides functionality for authenticating users using PAM (Pluggable Authentication Modules).
 pub struct Authenticator {
     service: String,
@@ -24,15 +42,16 @@ impl Authenticator {
         }
     }
 
-    /// Authenticates a user using their credentials.
+    /// Authenticates a user using their credentials and runs the PAM account stage. The PAM session is not opened:
+    /// it is opened with `Authentication::open_session` once the other login checks (such as the second factor) have passed.
     ///
     /// # Arguments
     /// * `credentials` - The user's credentials (username and password).
     ///
     /// # Returns
-    /// A `Result` containing an `EnvList` of environment variables if authentication succeeds,
+    /// A `Result` containing the `Authentication` if authentication succeeds,
     /// or an `ApplicationError` if authentication fails.
-    pub fn authenticate(&self, credentials: &Credentials) -> Result<EnvList, ApplicationError> {
+    pub fn authenticate(&self, credentials: &Credentials) -> Result<Authentication, ApplicationError> {
         let service = &self.service;
         debug!("Authenticating user {} for service {}", credentials.username(), service);
         let conversation =
@@ -40,7 +59,45 @@ impl Authenticator {
         let mut context = Context::new(service, None, conversation)?;
 
         context.authenticate(Flag::NONE)?;
+        context.acct_mgmt(Flag::NONE)?;
+        Ok(Authentication { context })
+    }
+
+    /// Runs the PAM account and session stages for a user that has already been authenticated
+    /// by other means (for example with a signed login ticket). The password step is skipped.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the pre-authenticated user.
+    ///
+    /// # Returns
+    /// A `Result` containing an `EnvList` of environment variables if the account is valid,
+    /// or an `ApplicationError` if the account is not permitted.
+    pub fn authorize(&self, username: &str) -> Result<EnvList, ApplicationError> {
+        let service = &self.service;
+        debug!("Authorizing pre-authenticated user {} for service {}", username, service);
+        let conversation = Conversation::with_credentials(username, "");
+        let mut context = Context::new(service, Some(username), conversation)?;
+
+        context.acct_mgmt(Flag::NONE)?;
         let session = context.open_session(Flag::NONE)?;
         Ok(session.envlist())
     }
+
+    /// Runs the PAM account stage for a user, without a password and without opening a session,
+    /// to determine whether the account is still valid (not locked, expired or otherwise refused).
+    ///
+    /// # Arguments
+    /// * `username` - The username of the user.
+    ///
+    /// # Returns
+    /// A `Result` indicating the account is valid or an `ApplicationError` if it is not permitted.
+    pub fn validate_account(&self, username: &str) -> Result<(), ApplicationError> {
+        let service = &self.service;
+        debug!("Validating account of user {} for service {}", username, service);
+        let conversation = Conversation::with_credentials(username, "");
+        let mut context = Context::new(service, Some(username), conversation)?;
+
+        context.acct_mgmt(Flag::SILENT)?;
+        Ok(())
+    }
 }
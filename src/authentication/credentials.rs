@@ -1,7 +1,9 @@
-/// The `Credentials` struct represents a user's login credentials, including their username and password.
+/// The `Credentials` struct represents a user's login credentials, including their username and password
+/// and an optional one-time password for the second factor.
 pub struct Credentials {
     username: String,
     password: String,
+    otp: Option<String>,
 }
 
 impl Credentials {
@@ -14,7 +16,19 @@ impl Credentials {
     /// # Returns
     /// A new `Credentials` instance.
     pub fn new(username: String, password: String) -> Self {
-        Credentials { username, password }
+        Credentials { username, password, otp: None }
+    }
+
+    /// Adds a one-time password to the `Credentials`.
+    ///
+    /// # Arguments
+    /// * `otp` - The one-time password of the user.
+    ///
+    /// # Returns
+    /// The `Credentials` including the one-time password.
+    pub fn with_otp(mut self, otp: String) -> Self {
+        self.otp = Some(otp);
+        self
     }
 
     /// Returns the username of the user.
@@ -26,4 +40,9 @@ impl Credentials {
     pub fn password(&self) -> &str {
         &self.password
     }
+
+    /// Returns the one-time password of the user, if provided.
+    pub fn otp(&self) -> Option<&str> {
+        self.otp.as_deref()
+    }
 }
@@ -0,0 +1,100 @@
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::common::{Account, LoginWindowPolicy, LoginWindowSettings};
+
+/// The `LoginWindow` struct represents a parsed login window: the weekdays and hours during which
+/// the members of a set of groups are permitted (or, for a reservation, exclusively permitted) to log in.
+pub struct LoginWindow {
+    name: String,
+    groups: Vec<String>,
+    days: Vec<Weekday>,
+    start: NaiveTime,
+    end: NaiveTime,
+    policy: LoginWindowPolicy,
+    exempt_users: Vec<String>,
+    exempt_groups: Vec<String>,
+}
+
+impl LoginWindow {
+    /// Creates a `LoginWindow` from its settings.
+    ///
+    /// # Arguments
+    /// * `settings` - The login window settings.
+    ///
+    /// # Returns
+    /// An `Option` containing the `LoginWindow`, or `None` if the times or days are invalid.
+    pub fn from_settings(settings: &LoginWindowSettings) -> Option<Self> {
+        let start = NaiveTime::parse_from_str(settings.start(), "%H:%M").ok()?;
+        let end = NaiveTime::parse_from_str(settings.end(), "%H:%M").ok()?;
+        let days = if settings.days().is_empty() {
+            vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun]
+        } else {
+            settings.days()
+                .iter()
+                .map(|day| day.parse::<Weekday>().ok())
+                .collect::<Option<Vec<Weekday>>>()?
+        };
+
+        Some(Self {
+            name: settings.name().into(),
+            groups: settings.groups().to_vec(),
+            days,
+            start,
+            end,
+            policy: settings.policy(),
+            exempt_users: settings.exempt_users().to_vec(),
+            exempt_groups: settings.exempt_groups().to_vec(),
+        })
+    }
+
+    /// Returns the name of the window.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the policy of the window.
+    pub fn policy(&self) -> LoginWindowPolicy {
+        self.policy
+    }
+
+    /// Determines whether the window is open at a given time.
+    /// A window whose end is before its start spans midnight and belongs to the day on which it opens.
+    ///
+    /// # Arguments
+    /// * `at` - The local date and time.
+    ///
+    /// # Returns
+    /// `true` if the window is open, otherwise `false`.
+    pub fn is_open(&self, at: &NaiveDateTime) -> bool {
+        let time = at.time();
+        if self.start <= self.end {
+            self.days.contains(&at.weekday()) && time >= self.start && time < self.end
+        } else {
+            let previous_day = (*at - Duration::days(1)).weekday();
+            (self.days.contains(&at.weekday()) && time >= self.start) || (self.days.contains(&previous_day) && time < self.end)
+        }
+    }
+
+    /// Determines whether an account is a member of the groups of the window.
+    ///
+    /// # Arguments
+    /// * `account` - The account.
+    ///
+    /// # Returns
+    /// `true` if the account is a member of one of the groups, otherwise `false`.
+    pub fn is_member(&self, account: &Account) -> bool {
+        self.groups.iter().any(|group| account.is_member_of(group))
+    }
+
+    /// Determines whether an account is exempt from the window.
+    ///
+    /// # Arguments
+    /// * `account` - The account.
+    ///
+    /// # Returns
+    /// `true` if the account is an exempt user or a member of an exempt group, otherwise `false`.
+    pub fn is_exempt(&self, account: &Account) -> bool {
+        self.exempt_users.iter().any(|user| user == account.username()) ||
+            self.exempt_groups.iter().any(|group| account.is_member_of(group))
+    }
+}
@@ -1,5 +1,17 @@
-pub use authenticator::Authenticator;
+pub use access_control::AccessControl;
+pub use authenticator::{Authentication, Authenticator};
 pub use credentials::Credentials;
+pub use login_window::LoginWindow;
+pub use reconnection::ReconnectionTokenStore;
+pub use throttle::{Lockout, LoginThrottle};
+pub use ticket::{Ticket, TicketVerifier};
+pub use totp::TotpStore;
 
+mod access_control;
 mod authenticator;
 mod credentials;
+mod login_window;
+mod reconnection;
+mod throttle;
+mod ticket;
+mod totp;
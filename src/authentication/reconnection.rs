@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::Rng;
+use uuid::Uuid;
+
+use crate::common::ReconnectionSettings;
+
+/// The `ReconnectionToken` struct associates an issued token with the session it is bound to.
+struct ReconnectionToken {
+    session_id: Uuid,
+    expires: i64,
+}
+
+/// The `ReconnectionTokenStore` struct issues, validates and revokes the opaque tokens that allow
+/// a client to re-attach to an existing session without providing the user's credentials again.
+/// Tokens are bound to a session, expire after a configured time and are single use: redeeming a
+/// token invalidates it so that a new one must be issued.
+pub struct ReconnectionTokenStore {
+    ttl: u64,
+    tokens: Mutex<HashMap<String, ReconnectionToken>>,
+}
+
+impl ReconnectionTokenStore {
+    /// Creates a new `ReconnectionTokenStore` instance.
+    ///
+    /// # Arguments
+    /// * `settings` - The reconnection settings.
+    ///
+    /// # Returns
+    /// A new `ReconnectionTokenStore` instance.
+    pub fn new(settings: &ReconnectionSettings) -> Self {
+        Self {
+            ttl: settings.ttl(),
+            tokens: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Issues a new reconnection token for a session.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session the token is bound to.
+    ///
+    /// # Returns
+    /// An `Option` containing the token, or `None` if it could not be stored.
+    pub fn issue(&self, session_id: &Uuid) -> Option<String> {
+        let mut bytes = [0u8; 32];
+        rand::rng().fill(&mut bytes);
+        let token = URL_SAFE_NO_PAD.encode(bytes);
+
+        let now = chrono::Utc::now().timestamp();
+        if let Ok(mut tokens) = self.tokens.lock() {
+            tokens.retain(|_, token| token.expires > now);
+            tokens.insert(token.clone(), ReconnectionToken {
+                session_id: *session_id,
+                expires: now + self.ttl as i64,
+            });
+            return Some(token);
+        }
+        None
+    }
+
+    /// Redeems a reconnection token. The token is removed whether or not it is still valid.
+    ///
+    /// # Arguments
+    /// * `token` - The token to redeem.
+    ///
+    /// # Returns
+    /// An `Option` containing the id of the session the token is bound to, or `None` if the token is unknown or has expired.
+    pub fn redeem(&self, token: &str) -> Option<Uuid> {
+        let now = chrono::Utc::now().timestamp();
+        if let Ok(mut tokens) = self.tokens.lock() {
+            return tokens
+                .remove(token)
+                .filter(|token| token.expires > now)
+                .map(|token| token.session_id);
+        }
+        None
+    }
+
+    /// Revokes all the tokens bound to a session.
+    ///
+    /// # Arguments
+    /// * `session_id` - The id of the session.
+    pub fn revoke(&self, session_id: &Uuid) {
+        if let Ok(mut tokens) = self.tokens.lock() {
+            tokens.retain(|_, token| &token.session_id != session_id);
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use crate::common::{ApplicationError, RateLimitSettings};
+
+/// The `LoginAttempts` struct tracks the failed login attempts and lockouts of a username.
+struct LoginAttempts {
+    failures: u32,
+    last_failure: i64,
+    lockouts: u32,
+    locked_until: i64,
+}
+
+/// The `Lockout` struct describes a username that is currently locked out.
+#[derive(Clone)]
+pub struct Lockout {
+    username: String,
+    lockouts: u32,
+    locked_until: i64,
+}
+
+impl Lockout {
+    /// Returns the username that is locked out.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Returns the number of successive lockouts of the username.
+    pub fn lockouts(&self) -> u32 {
+        self.lockouts
+    }
+
+    /// Returns the end of the lockout as a unix timestamp.
+    pub fn locked_until(&self) -> i64 {
+        self.locked_until
+    }
+}
+
+/// The `LoginThrottle` struct limits the rate of login attempts, both per username and globally,
+/// so that PAM cannot be hammered by anything that can reach the IPC socket.
+///
+/// A username is locked out after a number of failed attempts within a window. The duration of the lockout
+/// doubles with each successive lockout, up to a maximum, and is reset by a successful login.
+pub struct LoginThrottle {
+    settings: RateLimitSettings,
+    users: Mutex<HashMap<String, LoginAttempts>>,
+    attempts: Mutex<VecDeque<i64>>,
+}
+
+impl LoginThrottle {
+    /// Creates a new `LoginThrottle` instance.
+    ///
+    /// # Arguments
+    /// * `settings` - The rate limit settings.
+    ///
+    /// # Returns
+    /// A new `LoginThrottle` instance.
+    pub fn new(settings: &RateLimitSettings) -> Self {
+        Self {
+            settings: settings.clone(),
+            users: Mutex::new(HashMap::new()),
+            attempts: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Checks whether a login attempt is permitted for a username and records the attempt against the global limit.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the login attempt.
+    ///
+    /// # Returns
+    /// A `Result` indicating the attempt is permitted or a locked out `ApplicationError`.
+    pub fn check(&self, username: &str) -> Result<(), ApplicationError> {
+        let now = chrono::Utc::now().timestamp();
+
+        if let Ok(users) = self.users.lock() {
+            if let Some(attempts) = users.get(username) {
+                if attempts.locked_until > now {
+                    return Err(ApplicationError::locked_out(format!(
+                        "User {} is locked out, retry in {} seconds",
+                        username,
+                        attempts.locked_until - now
+                    )));
+                }
+            }
+        }
+
+        if let Ok(mut attempts) = self.attempts.lock() {
+            let global_window = self.settings.global_window() as i64;
+            while attempts.front().is_some_and(|attempt| now - attempt >= global_window) {
+                attempts.pop_front();
+            }
+            if attempts.len() >= self.settings.global_max_attempts() as usize {
+                let retry_in = attempts.front().map_or(global_window, |attempt| attempt + global_window - now);
+                warn!("Global login rate limit reached, rejecting login attempt for user {}", username);
+                return Err(ApplicationError::locked_out(format!(
+                    "Too many login attempts, retry in {} seconds",
+                    retry_in
+                )));
+            }
+            attempts.push_back(now);
+        }
+
+        Ok(())
+    }
+
+    /// Records a failed login attempt for a username, locking it out if the maximum number of attempts is reached.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the failed login attempt.
+    pub fn record_failure(&self, username: &str) {
+        let now = chrono::Utc::now().timestamp();
+        if let Ok(mut users) = self.users.lock() {
+            self.purge(&mut users, now);
+
+            let attempts = users.entry(username.to_string()).or_insert(LoginAttempts {
+                failures: 0,
+                last_failure: now,
+                lockouts: 0,
+                locked_until: 0,
+            });
+            if now - attempts.last_failure > self.settings.window() as i64 {
+                attempts.failures = 0;
+            }
+            attempts.failures += 1;
+            attempts.last_failure = now;
+
+            if attempts.failures >= self.settings.max_attempts() {
+                let duration = self.settings.lockout()
+                    .saturating_mul(2u64.saturating_pow(attempts.lockouts))
+                    .min(self.settings.max_lockout());
+                attempts.failures = 0;
+                attempts.lockouts += 1;
+                attempts.locked_until = now + duration as i64;
+                audit!("User {} locked out for {} seconds after {} failed login attempts (lockout {})", username, duration, self.settings.max_attempts(), attempts.lockouts);
+            }
+        }
+    }
+
+    /// Records a successful login for a username, resetting its failed attempts and lockouts.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the successful login.
+    pub fn record_success(&self, username: &str) {
+        if let Ok(mut users) = self.users.lock() {
+            users.remove(username);
+        }
+    }
+
+    /// Retrieves the usernames that are currently locked out.
+    ///
+    /// # Returns
+    /// A vector of `Lockout` instances.
+    pub fn lockouts(&self) -> Vec<Lockout> {
+        let now = chrono::Utc::now().timestamp();
+        if let Ok(users) = self.users.lock() {
+            let mut lockouts: Vec<Lockout> = users
+                .iter()
+                .filter(|(_, attempts)| attempts.locked_until > now)
+                .map(|(username, attempts)| Lockout {
+                    username: username.clone(),
+                    lockouts: attempts.lockouts,
+                    locked_until: attempts.locked_until,
+                })
+                .collect();
+            lockouts.sort_by(|a, b| a.username.cmp(&b.username));
+            return lockouts;
+        }
+        vec![]
+    }
+
+    /// Clears the lockout and failed attempts of a username, or of all usernames.
+    ///
+    /// # Arguments
+    /// * `username` - The username to clear, or `None` to clear all usernames.
+    ///
+    /// # Returns
+    /// The number of lockouts cleared.
+    pub fn clear(&self, username: Option<&str>) -> usize {
+        let now = chrono::Utc::now().timestamp();
+        if let Ok(mut users) = self.users.lock() {
+            let before = users.len();
+            let cleared = users
+                .iter()
+                .filter(|(name, attempts)| attempts.locked_until > now && username.is_none_or(|username| username == name.as_str()))
+                .count();
+            users.retain(|name, _| username.is_some_and(|username| username != name.as_str()));
+            debug!("Cleared login attempts of {} users", before - users.len());
+            return cleared;
+        }
+        0
+    }
+
+    /// Forgets the usernames whose lockout and last failure are older than the window (and maximum lockout),
+    /// so that their lockout duration starts again from the initial value.
+    ///
+    /// # Arguments
+    /// * `users` - The tracked usernames.
+    /// * `now` - The current unix timestamp.
+    fn purge(&self, users: &mut HashMap<String, LoginAttempts>, now: i64) {
+        let expiry = self.settings.window().max(self.settings.max_lockout()) as i64;
+        users.retain(|_, attempts| attempts.locked_until > now || now - attempts.last_failure <= expiry);
+    }
+}
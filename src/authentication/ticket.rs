@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::sync::Mutex;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::common::{ApplicationError, TicketKeySettings, TicketSettings};
+use crate::fs::{chmod, mkdir};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The `Ticket` struct represents the verified payload of a signed login ticket.
+///
+/// A ticket is encoded as `<payload>.<signature>` where the payload is the base64url encoded JSON
+/// `{"kid": "<key id>", "username": "<username>", "expires": <unix time>, "nonce": "<random string>"}`
+/// and the signature is the base64url encoded HMAC-SHA256 of the encoded payload. The `kid` field is optional:
+/// when it is omitted every configured key is tried.
+#[derive(Deserialize)]
+pub struct Ticket {
+    kid: Option<String>,
+    username: String,
+    expires: i64,
+    nonce: String,
+}
+
+impl Ticket {
+    /// Returns the username of the pre-authenticated user.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Returns the expiry of the ticket as a unix timestamp.
+    pub fn expires(&self) -> i64 {
+        self.expires
+    }
+
+    /// Returns the single-use nonce of the ticket.
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+}
+
+/// The `TicketVerifier` struct verifies signed login tickets against the configured keys
+/// and keeps track of the nonces that have already been used, persisted in a file only readable by root
+/// so that tickets cannot be replayed after a restart.
+pub struct TicketVerifier {
+    keys: Vec<TicketKeySettings>,
+    max_validity: u64,
+    path: String,
+    not_before: Option<i64>,
+    used_nonces: Mutex<HashMap<String, i64>>,
+}
+
+impl TicketVerifier {
+    /// Creates a new `TicketVerifier` instance, restoring the nonces used before a restart. If they cannot be
+    /// restored, the tickets that may have been issued before the verifier was created are rejected instead.
+    ///
+    /// # Arguments
+    /// * `settings` - The ticket settings containing the signing keys.
+    ///
+    /// # Returns
+    /// A new `TicketVerifier` instance.
+    pub fn new(settings: &TicketSettings) -> Self {
+        let path = settings.nonce_store_path().to_string();
+        let (used_nonces, not_before) = match Self::load(&path) {
+            Ok(used_nonces) => (used_nonces, None),
+            Err(error) => {
+                error!("{}", error);
+                warn!("Rejecting the login tickets issued before the session manager started");
+                (HashMap::new(), Some(chrono::Utc::now().timestamp()))
+            }
+        };
+
+        Self {
+            keys: settings.keys().to_vec(),
+            max_validity: settings.max_validity(),
+            path,
+            not_before,
+            used_nonces: Mutex::new(used_nonces),
+        }
+    }
+
+    /// Verifies the signature, expiry and nonce of a ticket.
+    /// The nonce is consumed on success so the same ticket cannot be used twice.
+    ///
+    /// # Arguments
+    /// * `ticket` - The encoded ticket.
+    ///
+    /// # Returns
+    /// A `Result` containing the verified `Ticket` or an `ApplicationError` if the ticket is invalid.
+    pub fn verify(&self, ticket: &str) -> Result<Ticket, ApplicationError> {
+        let (payload, signature) = ticket
+            .split_once('.')
+            .ok_or_else(|| ApplicationError::authentication("Malformed login ticket"))?;
+
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| ApplicationError::authentication("Malformed login ticket signature"))?;
+        let decoded_payload = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| ApplicationError::authentication("Malformed login ticket payload"))?;
+        let ticket: Ticket = serde_json::from_slice(&decoded_payload)
+            .map_err(|error| ApplicationError::authentication(format!("Invalid login ticket payload: {}", error)))?;
+
+        let is_signed = self.keys
+            .iter()
+            .filter(|key| ticket.kid.as_deref().is_none_or(|kid| kid == key.id()))
+            .any(|key| Self::is_signed_with(key, payload.as_bytes(), &signature));
+        if !is_signed {
+            return Err(ApplicationError::authentication(format!("Invalid signature for login ticket of user {}", ticket.username)));
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        if ticket.expires <= now {
+            return Err(ApplicationError::authentication(format!("Login ticket for user {} has expired", ticket.username)));
+        }
+        if ticket.expires - now > self.max_validity as i64 {
+            return Err(ApplicationError::authentication(format!("Login ticket for user {} is valid for too long", ticket.username)));
+        }
+        if ticket.nonce.is_empty() {
+            return Err(ApplicationError::authentication("Login ticket does not contain a nonce"));
+        }
+        // without the nonces used before the restart, a ticket issued before it may have been used already
+        if self.not_before.is_some_and(|not_before| ticket.expires - (self.max_validity as i64) < not_before) {
+            return Err(ApplicationError::authentication(format!("Login ticket for user {} may have been issued before the session manager started", ticket.username)));
+        }
+
+        let mut used_nonces = self.used_nonces
+            .lock()
+            .map_err(|_| ApplicationError::authentication("Could not verify login ticket nonce"))?;
+        // forget the nonces of tickets that have expired: they would be rejected anyway
+        used_nonces.retain(|_, expires| *expires > now);
+        if used_nonces.contains_key(&ticket.nonce) {
+            return Err(ApplicationError::authentication(format!("Login ticket for user {} has already been used", ticket.username)));
+        }
+        used_nonces.insert(ticket.nonce.clone(), ticket.expires);
+        self.save(&used_nonces)?;
+
+        Ok(ticket)
+    }
+
+    /// Loads the persisted nonces, if any.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the nonce store.
+    ///
+    /// # Returns
+    /// A `Result` containing the expiry of the ticket of each used nonce or an `ApplicationError`.
+    fn load(path: &str) -> Result<HashMap<String, i64>, ApplicationError> {
+        if !Path::new(path).exists() {
+            return Ok(HashMap::new());
+        }
+        let json = fs::read_to_string(path)
+            .map_err(|error| ApplicationError::environment(format!("Could not read the ticket nonce store {}: {}", path, error)))?;
+        serde_json::from_str(&json)
+            .map_err(|error| ApplicationError::environment(format!("Could not parse the ticket nonce store {}: {}", path, error)))
+    }
+
+    /// Saves the used nonces atomically, ensuring the file is only accessible by root.
+    ///
+    /// # Arguments
+    /// * `used_nonces` - The expiry of the ticket of each used nonce.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    fn save(&self, used_nonces: &HashMap<String, i64>) -> Result<(), ApplicationError> {
+        if let Some(directory) = Path::new(&self.path).parent().and_then(|directory| directory.to_str()) {
+            if !directory.is_empty() && !Path::new(directory).exists() {
+                mkdir(directory)?;
+                chmod(directory, 0o700)?;
+            }
+        }
+
+        let json = serde_json::to_string(used_nonces)
+            .map_err(|error| ApplicationError::environment(format!("Could not serialize the ticket nonce store: {}", error)))?;
+        let temporary_path = format!("{}.tmp", self.path);
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&temporary_path)
+            .map_err(|error| ApplicationError::environment(format!("Could not write the ticket nonce store {}: {}", temporary_path, error)))?;
+        file.write_all(json.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|error| ApplicationError::environment(format!("Could not write the ticket nonce store {}: {}", temporary_path, error)))?;
+        chmod(&temporary_path, 0o600)?;
+
+        fs::rename(&temporary_path, &self.path)
+            .map_err(|error| ApplicationError::environment(format!("Could not replace the ticket nonce store {}: {}", self.path, error)))
+    }
+
+    /// Checks the HMAC-SHA256 signature of a payload with a given key.
+    ///
+    /// # Arguments
+    /// * `key` - The key to verify the signature with.
+    /// * `payload` - The signed (encoded) payload.
+    /// * `signature` - The decoded signature.
+    ///
+    /// # Returns
+    /// `true` if the signature is valid, otherwise `false`.
+    fn is_signed_with(key: &TicketKeySettings, payload: &[u8], signature: &[u8]) -> bool {
+        match HmacSha256::new_from_slice(key.secret().as_bytes()) {
+            Ok(mut mac) => {
+                mac.update(payload);
+                mac.verify_slice(signature).is_ok()
+            }
+            Err(_) => false,
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+use crate::common::{ApplicationError, TotpSettings};
+use crate::fs::{chmod, mkdir};
+
+/// The number of digits of a one-time password.
+const TOTP_DIGITS: usize = 6;
+
+/// The duration in seconds of a time step.
+const TOTP_STEP: u64 = 30;
+
+/// The number of time steps before and after the current one that are accepted, to allow for clock drift.
+const TOTP_SKEW: u64 = 1;
+
+/// The `TotpEntry` struct represents the enrollment of a user in the TOTP store.
+#[derive(Serialize, Deserialize)]
+struct TotpEntry {
+    secret: String,
+    last_step: Option<u64>,
+}
+
+/// The `TotpStore` struct manages the TOTP secrets of the users, stored in a file only readable by root,
+/// and verifies the one-time passwords provided at login. Each time step can only be used once.
+pub struct TotpStore {
+    path: String,
+    issuer: String,
+    required: bool,
+    lock: Mutex<()>,
+}
+
+impl TotpStore {
+    /// Creates a new `TotpStore` instance.
+    ///
+    /// # Arguments
+    /// * `settings` - The TOTP settings.
+    ///
+    /// # Returns
+    /// A new `TotpStore` instance.
+    pub fn new(settings: &TotpSettings) -> Self {
+        Self {
+            path: settings.store_path().into(),
+            issuer: settings.issuer().into(),
+            required: settings.required(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Verifies the second factor of a user that has been authenticated by PAM.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the user.
+    /// * `otp` - The one-time password provided by the user, if any.
+    ///
+    /// # Returns
+    /// A `Result` indicating the second factor is valid (or not needed) or an `ApplicationError`.
+    pub fn verify(&self, username: &str, otp: Option<&str>) -> Result<(), ApplicationError> {
+        let _guard = self.lock.lock().map_err(|_| ApplicationError::authentication("Could not access the TOTP store"))?;
+        let mut entries = self.load()?;
+
+        let entry = match entries.get_mut(username) {
+            Some(entry) => entry,
+            None if self.required => return Err(ApplicationError::authentication(format!("User {} is not enrolled for the second factor", username))),
+            None => return Ok(()),
+        };
+
+        let otp = otp.ok_or_else(|| ApplicationError::authentication(format!("A one-time password is required for user {}", username)))?;
+        let totp = self.create_totp(username, &entry.secret)?;
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let current_step = now / TOTP_STEP;
+        let step = (current_step.saturating_sub(TOTP_SKEW)..=current_step + TOTP_SKEW)
+            .filter(|step| entry.last_step.is_none_or(|last_step| *step > last_step))
+            .find(|step| totp.generate(step * TOTP_STEP) == otp)
+            .ok_or_else(|| ApplicationError::authentication(format!("Invalid one-time password for user {}", username)))?;
+
+        entry.last_step = Some(step);
+        self.save(&entries)
+    }
+
+    /// Enrolls a user, replacing any existing secret.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the user.
+    ///
+    /// # Returns
+    /// A `Result` containing the otpauth URI to register in an authenticator application, or an `ApplicationError`.
+    pub fn enroll(&self, username: &str) -> Result<String, ApplicationError> {
+        let _guard = self.lock.lock().map_err(|_| ApplicationError::authentication("Could not access the TOTP store"))?;
+        let mut entries = self.load()?;
+
+        let secret = match Secret::generate_secret().to_encoded() {
+            Secret::Encoded(secret) => secret,
+            Secret::Raw(_) => return Err(ApplicationError::authentication("Could not encode the TOTP secret")),
+        };
+        let totp = self.create_totp(username, &secret)?;
+
+        entries.insert(username.into(), TotpEntry { secret, last_step: None });
+        self.save(&entries)?;
+
+        Ok(totp.get_url())
+    }
+
+    /// Removes the enrollment of a user.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the user.
+    ///
+    /// # Returns
+    /// A `Result` indicating whether the user was enrolled, or an `ApplicationError`.
+    pub fn reset(&self, username: &str) -> Result<bool, ApplicationError> {
+        let _guard = self.lock.lock().map_err(|_| ApplicationError::authentication("Could not access the TOTP store"))?;
+        let mut entries = self.load()?;
+        if entries.remove(username).is_some() {
+            self.save(&entries)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Creates the TOTP generator for a user.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the user, used as the account name.
+    /// * `secret` - The base32 encoded secret of the user.
+    ///
+    /// # Returns
+    /// A `Result` containing the `TOTP` or an `ApplicationError`.
+    fn create_totp(&self, username: &str, secret: &str) -> Result<TOTP, ApplicationError> {
+        let secret = Secret::Encoded(secret.into())
+            .to_bytes()
+            .map_err(|error| ApplicationError::authentication(format!("Invalid TOTP secret for user {}: {}", username, error)))?;
+        TOTP::new(Algorithm::SHA1, TOTP_DIGITS, TOTP_SKEW as u8, TOTP_STEP, secret, Some(self.issuer.clone()), username.into())
+            .map_err(|error| ApplicationError::authentication(format!("Could not create TOTP for user {}: {}", username, error)))
+    }
+
+    /// Loads the entries of the store. A missing store is empty.
+    ///
+    /// # Returns
+    /// A `Result` containing the entries by username or an `ApplicationError`.
+    fn load(&self) -> Result<HashMap<String, TotpEntry>, ApplicationError> {
+        if !Path::new(&self.path).exists() {
+            return Ok(HashMap::new());
+        }
+        let json = fs::read_to_string(&self.path)
+            .map_err(|error| ApplicationError::environment(format!("Could not read the TOTP store {}: {}", self.path, error)))?;
+        serde_json::from_str(&json)
+            .map_err(|error| ApplicationError::environment(format!("Could not parse the TOTP store {}: {}", self.path, error)))
+    }
+
+    /// Saves the entries of the store atomically, ensuring the file is only accessible by root.
+    ///
+    /// # Arguments
+    /// * `entries` - The entries by username.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    fn save(&self, entries: &HashMap<String, TotpEntry>) -> Result<(), ApplicationError> {
+        if let Some(directory) = Path::new(&self.path).parent().and_then(|directory| directory.to_str()) {
+            if !directory.is_empty() && !Path::new(directory).exists() {
+                mkdir(directory)?;
+                chmod(directory, 0o700)?;
+            }
+        }
+
+        let json = serde_json::to_string_pretty(entries)
+            .map_err(|error| ApplicationError::environment(format!("Could not serialize the TOTP store: {}", error)))?;
+        let temporary_path = format!("{}.tmp", self.path);
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&temporary_path)
+            .map_err(|error| ApplicationError::environment(format!("Could not write the TOTP store {}: {}", temporary_path, error)))?;
+        file.write_all(json.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|error| ApplicationError::environment(format!("Could not write the TOTP store {}: {}", temporary_path, error)))?;
+        chmod(&temporary_path, 0o600)?;
+
+        fs::rename(&temporary_path, &self.path)
+            .map_err(|error| ApplicationError::environment(format!("Could not replace the TOTP store {}: {}", self.path, error)))
+    }
+}
@@ -1,7 +1,7 @@
 use nix::unistd::{User, Uid};
 use structopt::StructOpt;
 
-use webx_session_manager::{authentication::{Credentials, Authenticator}, common::{ApplicationError, ScreenResolution, Account}, services::Client};
+use webx_session_manager::{authentication::{Credentials, Authenticator}, common::{ApplicationError, ScreenResolution, Account, KeyboardLayout, Monitor, Notification, NotificationUrgency, SessionFilter, SessionOptions, SessionSort, SessionState}, services::Client};
 use rpassword::read_password;
 use std::io::Write;
 
@@ -11,6 +11,58 @@ use std::io::Write;
 enum Command {
     /// Lists all active sessions.
     Who {
+        /// Only lists the sessions of a user.
+        #[structopt(long)]
+        username: Option<String>,
+
+        /// Only lists the sessions of a user ID.
+        #[structopt(long)]
+        uid: Option<u32>,
+
+        /// Only lists the sessions in a state (active or flagged).
+        #[structopt(long)]
+        state: Option<SessionState>,
+
+        /// Only lists the sessions having a label, as KEY=VALUE. Can be repeated.
+        #[structopt(long = "label", parse(try_from_str = parse_label))]
+        labels: Vec<(String, String)>,
+
+        /// Only lists the session running on a display (for example :60).
+        #[structopt(long)]
+        display: Option<String>,
+
+        /// Only lists the sessions created after a unix timestamp.
+        #[structopt(long)]
+        created_after: Option<i64>,
+
+        /// Only lists the sessions created before a unix timestamp.
+        #[structopt(long)]
+        created_before: Option<i64>,
+
+        /// Sorts the sessions (created, username, uid or display).
+        #[structopt(long)]
+        sort: Option<SessionSort>,
+
+        /// Sorts the sessions in descending order.
+        #[structopt(long)]
+        descending: bool,
+
+        /// The IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager.ipc")]
+        ipc: String,
+    },
+    /// Retrieves a single session.
+    Get {
+        /// The session ID.
+        #[structopt(short, long)]
+        id: String,
+
+        /// The IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager.ipc")]
+        ipc: String,
+    },
+    /// Lists the desktop environments that can be chosen at login.
+    Desktops {
         /// The IPC path to the WebX Session Manager server.
         #[structopt(long, default_value = "/tmp/webx-session-manager.ipc")]
         ipc: String,
@@ -29,6 +81,164 @@ enum Command {
         #[structopt(short, long)]
         height: u32,
 
+        /// The one-time password, for users enrolled in the TOTP second factor.
+        #[structopt(long)]
+        otp: Option<String>,
+
+        /// The display server backend (xorgxrdp, xvfb, xdummy or xvnc). The server default is used if omitted.
+        #[structopt(long)]
+        display_server: Option<String>,
+
+        /// The id of the desktop environment (see the desktops command). The default window manager is used if omitted.
+        #[structopt(long)]
+        desktop: Option<String>,
+
+        /// The name of the session profile (such as a kiosk profile).
+        #[structopt(long)]
+        profile: Option<String>,
+
+        /// A monitor of a multi-monitor layout, as widthxheight+x+y (followed by * for the primary monitor). Can be repeated.
+        #[structopt(long = "monitor")]
+        monitors: Vec<Monitor>,
+
+        /// The DPI of the display. The server default is used if omitted.
+        #[structopt(long)]
+        dpi: Option<u32>,
+
+        /// The color depth of the display (16 or 24). The server default is used if omitted.
+        #[structopt(long)]
+        depth: Option<u32>,
+
+        /// The XKB keyboard layout (for example us or fr,us). The server default is used if omitted.
+        #[structopt(long)]
+        keyboard_layout: Option<String>,
+
+        /// The XKB keyboard variant (for example intl), used with the keyboard layout.
+        #[structopt(long)]
+        keyboard_variant: Option<String>,
+
+        /// The XKB keyboard model (for example pc105), used with the keyboard layout.
+        #[structopt(long)]
+        keyboard_model: Option<String>,
+
+        /// The locale of the desktop (for example fr_CH.UTF-8).
+        #[structopt(long)]
+        locale: Option<String>,
+
+        /// The timezone of the desktop (for example Europe/Zurich).
+        #[structopt(long)]
+        timezone: Option<String>,
+
+        /// An environment variable for the session, as NAME=VALUE. Can be repeated.
+        #[structopt(long = "env", parse(try_from_str = parse_environment_variable))]
+        environment: Vec<(String, String)>,
+
+        /// A label of the session, as KEY=VALUE. Can be repeated.
+        #[structopt(long = "label", parse(try_from_str = parse_label))]
+        labels: Vec<(String, String)>,
+
+        /// The IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager.ipc")]
+        ipc: String,
+    },
+    /// Logs in a pre-authenticated user with a signed login ticket and creates a new session.
+    LoginTicket {
+        /// The signed login ticket.
+        #[structopt(short, long)]
+        ticket: String,
+
+        /// The screen width for the session.
+        #[structopt(short, long)]
+        width: u32,
+
+        /// The screen height for the session.
+        #[structopt(short, long)]
+        height: u32,
+
+        /// The display server backend (xorgxrdp, xvfb, xdummy or xvnc). The server default is used if omitted.
+        #[structopt(long)]
+        display_server: Option<String>,
+
+        /// The id of the desktop environment (see the desktops command). The default window manager is used if omitted.
+        #[structopt(long)]
+        desktop: Option<String>,
+
+        /// The name of the session profile (such as a kiosk profile).
+        #[structopt(long)]
+        profile: Option<String>,
+
+        /// A monitor of a multi-monitor layout, as widthxheight+x+y (followed by * for the primary monitor). Can be repeated.
+        #[structopt(long = "monitor")]
+        monitors: Vec<Monitor>,
+
+        /// The DPI of the display. The server default is used if omitted.
+        #[structopt(long)]
+        dpi: Option<u32>,
+
+        /// The color depth of the display (16 or 24). The server default is used if omitted.
+        #[structopt(long)]
+        depth: Option<u32>,
+
+        /// The XKB keyboard layout (for example us or fr,us). The server default is used if omitted.
+        #[structopt(long)]
+        keyboard_layout: Option<String>,
+
+        /// The XKB keyboard variant (for example intl), used with the keyboard layout.
+        #[structopt(long)]
+        keyboard_variant: Option<String>,
+
+        /// The XKB keyboard model (for example pc105), used with the keyboard layout.
+        #[structopt(long)]
+        keyboard_model: Option<String>,
+
+        /// The locale of the desktop (for example fr_CH.UTF-8).
+        #[structopt(long)]
+        locale: Option<String>,
+
+        /// The timezone of the desktop (for example Europe/Zurich).
+        #[structopt(long)]
+        timezone: Option<String>,
+
+        /// An environment variable for the session, as NAME=VALUE. Can be repeated.
+        #[structopt(long = "env", parse(try_from_str = parse_environment_variable))]
+        environment: Vec<(String, String)>,
+
+        /// A label of the session, as KEY=VALUE. Can be repeated.
+        #[structopt(long = "label", parse(try_from_str = parse_label))]
+        labels: Vec<(String, String)>,
+
+        /// The IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager.ipc")]
+        ipc: String,
+    },
+    /// Retrieves an existing session using a reconnection token.
+    Reconnect {
+        /// The reconnection token returned by a previous login.
+        #[structopt(short, long)]
+        token: String,
+
+        /// The IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager.ipc")]
+        ipc: String,
+    },
+    /// Changes the screen resolution of a running session.
+    Resize {
+        /// The session ID to resize.
+        #[structopt(short, long)]
+        id: String,
+
+        /// The new screen width.
+        #[structopt(short, long)]
+        width: u32,
+
+        /// The new screen height.
+        #[structopt(short, long)]
+        height: u32,
+
+        /// A monitor of a multi-monitor layout, as widthxheight+x+y (followed by * for the primary monitor). Can be repeated.
+        #[structopt(long = "monitor")]
+        monitors: Vec<Monitor>,
+
         /// The IPC path to the WebX Session Manager server.
         #[structopt(long, default_value = "/tmp/webx-session-manager.ipc")]
         ipc: String,
@@ -43,6 +253,174 @@ enum Command {
         #[structopt(long, default_value = "/tmp/webx-session-manager.ipc")]
         ipc: String,
     },
+    /// Logs out all the sessions of a user, having labels, or all the sessions.
+    LogoutSessions {
+        /// Only logs out the sessions of a user.
+        #[structopt(long)]
+        username: Option<String>,
+
+        /// Only logs out the sessions of a user ID.
+        #[structopt(long)]
+        uid: Option<u32>,
+
+        /// Only logs out the sessions having a label, as KEY=VALUE. Can be repeated.
+        #[structopt(long = "label", parse(try_from_str = parse_label))]
+        labels: Vec<(String, String)>,
+
+        /// Logs out all the sessions (required when no other criteria are given).
+        #[structopt(long)]
+        all: bool,
+
+        /// The admin IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager-admin.ipc")]
+        ipc: String,
+    },
+    /// Grants another user access to a session.
+    Grant {
+        /// The session ID.
+        #[structopt(short, long)]
+        id: String,
+
+        /// The username of the user granted access.
+        #[structopt(short, long)]
+        grantee: String,
+
+        /// Only allows the grantee to view the session.
+        #[structopt(long)]
+        view_only: bool,
+
+        /// The cookie of the session, required unless the request is sent to the admin socket.
+        #[structopt(long)]
+        cookie: Option<String>,
+
+        /// The IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager.ipc")]
+        ipc: String,
+    },
+    /// Revokes the access to a session granted to a user.
+    RevokeGrant {
+        /// The session ID.
+        #[structopt(short, long)]
+        id: String,
+
+        /// The username of the user granted access.
+        #[structopt(short, long)]
+        grantee: String,
+
+        /// The cookie of the session, required unless the request is sent to the admin socket.
+        #[structopt(long)]
+        cookie: Option<String>,
+
+        /// The IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager.ipc")]
+        ipc: String,
+    },
+    /// Lists the usernames that are locked out after too many failed login attempts (admin).
+    Lockouts {
+        /// The admin IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager-admin.ipc")]
+        ipc: String,
+    },
+    /// Clears the lockout of a username, or of all usernames (admin).
+    ClearLockouts {
+        /// The username to clear. All usernames are cleared if omitted.
+        #[structopt(short, long)]
+        username: Option<String>,
+
+        /// The admin IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager-admin.ipc")]
+        ipc: String,
+    },
+    /// Enrolls a user in the TOTP second factor and prints the otpauth URI (admin).
+    TotpEnroll {
+        /// The username of the user to enroll.
+        #[structopt(short, long)]
+        username: String,
+
+        /// The admin IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager-admin.ipc")]
+        ipc: String,
+    },
+    /// Removes the TOTP enrollment of a user (admin).
+    TotpReset {
+        /// The username of the user.
+        #[structopt(short, long)]
+        username: String,
+
+        /// The admin IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager-admin.ipc")]
+        ipc: String,
+    },
+    /// Replaces the cookie given to the clients of a session, disconnecting those using the previous cookie (admin).
+    RotateCookie {
+        /// The session ID.
+        #[structopt(short, long)]
+        id: String,
+
+        /// The admin IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager-admin.ipc")]
+        ipc: String,
+    },
+    /// Enables the drain mode: new logins are rejected while the existing sessions keep running.
+    Drain {
+        /// Allows the users that already have a session to log in to it.
+        #[structopt(long)]
+        allow_existing: bool,
+
+        /// Terminates the remaining sessions after a number of minutes.
+        #[structopt(long)]
+        minutes: Option<i64>,
+
+        /// The admin IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager-admin.ipc")]
+        ipc: String,
+    },
+    /// Disables the drain mode.
+    Undrain {
+        /// The admin IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager-admin.ipc")]
+        ipc: String,
+    },
+    /// Retrieves the drain mode.
+    DrainStatus {
+        /// The admin IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager-admin.ipc")]
+        ipc: String,
+    },
+    /// Shows a desktop notification in sessions.
+    Notify {
+        /// The summary (title) of the notification.
+        #[structopt(short, long)]
+        summary: String,
+
+        /// The body of the notification.
+        #[structopt(short, long)]
+        body: Option<String>,
+
+        /// The urgency of the notification (low, normal or critical).
+        #[structopt(long, default_value = "normal")]
+        urgency: NotificationUrgency,
+
+        /// Only notifies a session. Can be repeated.
+        #[structopt(long = "id")]
+        ids: Vec<String>,
+
+        /// Only notifies the sessions of a user.
+        #[structopt(long)]
+        username: Option<String>,
+
+        /// Only notifies the sessions having a label, as KEY=VALUE. Can be repeated.
+        #[structopt(long = "label", parse(try_from_str = parse_label))]
+        labels: Vec<(String, String)>,
+
+        /// Notifies all the sessions (required when no other criteria are given).
+        #[structopt(long)]
+        all: bool,
+
+        /// The admin IPC path to the WebX Session Manager server.
+        #[structopt(long, default_value = "/tmp/webx-session-manager-admin.ipc")]
+        ipc: String,
+    },
     /// Authenticates a user using the specified PAM service.
     Authenticate {
         /// The username of the user.
@@ -55,6 +433,28 @@ enum Command {
     },
 }
 
+/// Parses an environment variable given as NAME=VALUE.
+///
+/// # Arguments
+/// * `value` - The environment variable.
+///
+/// # Returns
+/// A `Result` containing the name and value or an error message.
+fn parse_environment_variable(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .ok_or_else(|| format!("Invalid environment variable {}: expected NAME=VALUE", value))
+}
+
+/// Parses a label given as KEY=VALUE.
+fn parse_label(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .ok_or_else(|| format!("Invalid label {}: expected KEY=VALUE", value))
+}
+
 /// The main entry point for the WebX Session Manager client.
 /// This program allows users to interact with the WebX Session Manager server.
 pub fn main() -> Result<(), ApplicationError> {
@@ -66,23 +466,134 @@ pub fn main() -> Result<(), ApplicationError> {
     
     let command = Command::from_args();
     match command {
-        Command::Who {ipc} => {
+        Command::Who { ipc, username, uid, state, labels, display, created_after, created_before, sort, descending } => {
+            let filter = SessionFilter::default()
+                .with_username(username)
+                .with_uid(uid)
+                .with_state(state)
+                .with_labels(labels.into_iter().collect())
+                .with_display(display)
+                .with_created(created_after, created_before)
+                .with_sort(sort, descending);
+            let client = Client::new(ipc)?;
+            client.who(filter)?
+        }
+        Command::Get { ipc, id } => {
+            let client = Client::new(ipc)?;
+            client.get(id)?
+        }
+        Command::Desktops { ipc } => {
             let client = Client::new(ipc)?;
-            client.who()?
+            client.desktops()?
         }
-        Command::Login { ipc, username, width, height } => {
+        Command::Login { ipc, username, width, height, otp, display_server, desktop, profile, monitors, dpi, depth, keyboard_layout, keyboard_variant, keyboard_model, locale, timezone, environment, labels } => {
             print!("Enter password:");
             std::io::stdout().flush().unwrap();
             let password = read_password().unwrap();
-            let credentials = Credentials::new(username, password);
-            let resolution = ScreenResolution::new(width, height);
+            let mut credentials = Credentials::new(username, password);
+            if let Some(otp) = otp {
+                credentials = credentials.with_otp(otp);
+            }
+            let resolution = ScreenResolution::new(width, height).with_monitors(monitors);
+            let options = SessionOptions::default()
+                .with_display_server(display_server)
+                .with_desktop(desktop)
+                .with_profile(profile)
+                .with_dpi(dpi)
+                .with_depth(depth)
+                .with_keyboard(keyboard_layout.map(|layout| KeyboardLayout::new(keyboard_model, layout, keyboard_variant)))
+                .with_locale(locale)
+                .with_timezone(timezone)
+                .with_environment(environment.into_iter().collect())
+                .with_labels(labels.into_iter().collect());
+            let client = Client::new(ipc)?;
+            client.login(credentials, resolution, options)?;
+        },
+        Command::LoginTicket { ipc, ticket, width, height, display_server, desktop, profile, monitors, dpi, depth, keyboard_layout, keyboard_variant, keyboard_model, locale, timezone, environment, labels } => {
+            let resolution = ScreenResolution::new(width, height).with_monitors(monitors);
+            let options = SessionOptions::default()
+                .with_display_server(display_server)
+                .with_desktop(desktop)
+                .with_profile(profile)
+                .with_dpi(dpi)
+                .with_depth(depth)
+                .with_keyboard(keyboard_layout.map(|layout| KeyboardLayout::new(keyboard_model, layout, keyboard_variant)))
+                .with_locale(locale)
+                .with_timezone(timezone)
+                .with_environment(environment.into_iter().collect())
+                .with_labels(labels.into_iter().collect());
+            let client = Client::new(ipc)?;
+            client.login_with_ticket(ticket, resolution, options)?;
+        },
+        Command::Reconnect { ipc, token } => {
             let client = Client::new(ipc)?;
-            client.login(credentials, resolution)?;
+            client.reconnect(token)?;
         },
+        Command::Resize { ipc, id, width, height, monitors } => {
+            let resolution = ScreenResolution::new(width, height).with_monitors(monitors);
+            let client = Client::new(ipc)?;
+            client.resize(id, resolution)?;
+        }
         Command::Logout  { ipc, id} => {
             let client = Client::new(ipc)?;
             client.logout(id)?; 
         }
+        Command::LogoutSessions { ipc, username, uid, labels, all } => {
+            let filter = SessionFilter::default()
+                .with_username(username)
+                .with_uid(uid)
+                .with_labels(labels.into_iter().collect());
+            let client = Client::new(ipc)?;
+            client.logout_sessions(filter, all)?;
+        }
+        Command::Lockouts { ipc } => {
+            let client = Client::new(ipc)?;
+            client.lockouts()?;
+        }
+        Command::ClearLockouts { ipc, username } => {
+            let client = Client::new(ipc)?;
+            client.clear_lockouts(username)?;
+        }
+        Command::TotpEnroll { ipc, username } => {
+            let client = Client::new(ipc)?;
+            client.totp_enroll(username)?;
+        }
+        Command::TotpReset { ipc, username } => {
+            let client = Client::new(ipc)?;
+            client.totp_reset(username)?;
+        }
+        Command::Grant { ipc, id, grantee, view_only, cookie } => {
+            let client = Client::new(ipc)?;
+            client.grant(id, grantee, view_only, cookie)?;
+        }
+        Command::RevokeGrant { ipc, id, grantee, cookie } => {
+            let client = Client::new(ipc)?;
+            client.revoke_grant(id, grantee, cookie)?;
+        }
+        Command::RotateCookie { ipc, id } => {
+            let client = Client::new(ipc)?;
+            client.rotate_cookie(id)?;
+        }
+        Command::Drain { ipc, allow_existing, minutes } => {
+            let deadline = minutes.map(|minutes| chrono::Utc::now().timestamp() + minutes * 60);
+            let client = Client::new(ipc)?;
+            client.drain(allow_existing, deadline)?;
+        }
+        Command::Undrain { ipc } => {
+            let client = Client::new(ipc)?;
+            client.undrain()?;
+        }
+        Command::DrainStatus { ipc } => {
+            let client = Client::new(ipc)?;
+            client.drain_status()?;
+        }
+        Command::Notify { ipc, summary, body, urgency, ids, username, labels, all } => {
+            let filter = SessionFilter::default()
+                .with_username(username)
+                .with_labels(labels.into_iter().collect());
+            let client = Client::new(ipc)?;
+            client.notify(ids, filter, all, Notification::new(summary, body, urgency))?;
+        }
         Command::Authenticate { service, username} => {
             print!("Enter password:");
             std::io::stdout().flush().unwrap();
@@ -91,7 +602,7 @@ pub fn main() -> Result<(), ApplicationError> {
             let credentials = Credentials::new(username, password);
             let authenticator = Authenticator::new(service);
         
-            match authenticator.authenticate(&credentials) {
+            match authenticator.authenticate(&credentials).and_then(|authentication| authentication.open_session()) {
                 Ok(environment) => {
                     println!("Authenticated user: {}", &credentials.username());
                     if let Ok(Some(user)) = User::from_name(credentials.username()) {
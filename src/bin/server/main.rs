@@ -155,6 +155,26 @@ fn setup_logging(settings: &Settings) -> Result<(), fern::InitError> {
         }
     }
 
-    base_config.apply()?;
+    let mut dispatch = fern::Dispatch::new().chain(base_config);
+
+    // audit messages are also written to a dedicated file if configured
+    if let Some(audit_config) = &logging_config.audit() {
+        if audit_config.enabled().unwrap_or(false) {
+            let audit_file = std::fs::OpenOptions::new()
+                .append(true)
+                .create(true)
+                .open(audit_config.path())?;
+            let audit_dispatch = fern::Dispatch::new()
+                .filter(|metadata| metadata.target() == "audit")
+                .format(|out, message, _record| {
+                    out.finish(format_args!("[{}] {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"), message))
+                })
+                .level(log::LevelFilter::Info)
+                .chain(audit_file);
+            dispatch = dispatch.chain(audit_dispatch);
+        }
+    }
+
+    dispatch.apply()?;
     Ok(())
 }
@@ -1,7 +1,7 @@
 use std::fmt;
 
 use nix::unistd::User;
-use users::get_user_groups;
+use users::{get_group_by_name, get_user_groups};
 
 /// The `Account` struct represents a user account in the system.
 pub struct Account {
@@ -59,6 +59,18 @@ impl Account {
         &self.groups
     }
 
+    /// Determines whether the account is a member of a group, either as its primary group or as a supplementary group.
+    ///
+    /// # Arguments
+    /// * `group_name` - The name of the group.
+    ///
+    /// # Returns
+    /// `true` if the account is a member of the group, `false` otherwise or if the group does not exist.
+    pub fn is_member_of(&self, group_name: &str) -> bool {
+        get_group_by_name(group_name)
+            .is_some_and(|group| self.gid == group.gid() || self.groups.contains(&group.gid()))
+    }
+
     /// Creates an `Account` instance from a `User`.
     ///
     /// # Arguments
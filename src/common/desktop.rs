@@ -0,0 +1,127 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// The `Desktop` struct represents a desktop environment described by an xsessions `.desktop` entry.
+#[derive(Clone)]
+pub struct Desktop {
+    id: String,
+    name: String,
+    comment: String,
+    exec: String,
+    desktop_names: Vec<String>,
+}
+
+impl Desktop {
+    /// Parses a desktop entry file. The id of the desktop is the file name without the `.desktop` extension.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the `.desktop` file.
+    ///
+    /// # Returns
+    /// An `Option` containing the `Desktop`, or `None` if the entry is invalid, hidden or its executable is not installed.
+    pub fn from_file(path: &Path) -> Option<Self> {
+        let id = path.file_stem()?.to_str()?.to_string();
+        let content = fs::read_to_string(path).ok()?;
+
+        let mut in_desktop_entry = false;
+        let mut name = None;
+        let mut comment = None;
+        let mut exec = None;
+        let mut try_exec = None;
+        let mut desktop_names = vec![];
+        let mut hidden = false;
+
+        for line in content.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_desktop_entry = line == "[Desktop Entry]";
+                continue;
+            }
+            if !in_desktop_entry {
+                continue;
+            }
+            // localised keys (Name[fr]=...) are ignored
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value.trim();
+                match key.trim() {
+                    "Name" => name = Some(value.to_string()),
+                    "Comment" => comment = Some(value.to_string()),
+                    "Exec" => exec = Some(value.to_string()),
+                    "TryExec" => try_exec = Some(value.to_string()),
+                    "DesktopNames" => desktop_names = value.split(';').filter(|name| !name.is_empty()).map(String::from).collect(),
+                    "Hidden" | "NoDisplay" => hidden = hidden || value == "true",
+                    _ => {}
+                }
+            }
+        }
+
+        if hidden || try_exec.is_some_and(|try_exec| !Desktop::is_installed(&try_exec)) {
+            return None;
+        }
+
+        Some(Self {
+            id,
+            name: name?,
+            comment: comment.unwrap_or_default(),
+            exec: exec?,
+            desktop_names,
+        })
+    }
+
+    /// Returns the id of the desktop.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the name of the desktop.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the description of the desktop.
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    /// Returns the command starting the desktop.
+    pub fn exec(&self) -> &str {
+        &self.exec
+    }
+
+    /// Returns the value of `XDG_CURRENT_DESKTOP` for the desktop: its desktop names separated by colons, or its name.
+    pub fn current_desktop(&self) -> String {
+        if self.desktop_names.is_empty() {
+            self.name.clone()
+        } else {
+            self.desktop_names.join(":")
+        }
+    }
+
+    /// Determines whether an executable exists, either as an absolute path or in the `PATH`.
+    ///
+    /// # Arguments
+    /// * `executable` - The executable to look for.
+    ///
+    /// # Returns
+    /// `true` if the executable exists, otherwise `false`.
+    fn is_installed(executable: &str) -> bool {
+        if executable.starts_with('/') {
+            return Path::new(executable).exists();
+        }
+        std::env::var("PATH")
+            .unwrap_or_else(|_| "/usr/local/bin:/usr/bin:/bin".into())
+            .split(':')
+            .any(|directory| Path::new(directory).join(executable).exists())
+    }
+}
+
+impl fmt::Display for Desktop {
+    /// Formats the `Desktop` for display.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_struct("Desktop")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("exec", &self.exec)
+            .finish()
+    }
+}
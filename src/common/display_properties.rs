@@ -0,0 +1,72 @@
+use std::fmt;
+
+/// The `DisplayProperties` struct contains the properties of the display of a session besides its resolution:
+/// its resolution in dots per inch and its color depth.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DisplayProperties {
+    dpi: u32,
+    depth: u32,
+}
+
+impl DisplayProperties {
+    /// The minimum supported DPI.
+    pub const MIN_DPI: u32 = 48;
+
+    /// The maximum supported DPI.
+    pub const MAX_DPI: u32 = 480;
+
+    /// The supported color depths.
+    pub const DEPTHS: [u32; 2] = [16, 24];
+
+    /// Creates a new `DisplayProperties` instance.
+    ///
+    /// # Arguments
+    /// * `dpi` - The resolution of the display in dots per inch.
+    /// * `depth` - The color depth of the display in bits per pixel.
+    ///
+    /// # Returns
+    /// A new `DisplayProperties` instance.
+    pub fn new(dpi: u32, depth: u32) -> Self {
+        Self {
+            dpi,
+            depth,
+        }
+    }
+
+    /// Returns the resolution of the display in dots per inch.
+    pub fn dpi(&self) -> u32 {
+        self.dpi
+    }
+
+    /// Returns the color depth of the display in bits per pixel.
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Determines whether the DPI and color depth are supported.
+    ///
+    /// # Returns
+    /// `true` if the DPI is within the supported range and the color depth is supported, otherwise `false`.
+    pub fn is_supported(&self) -> bool {
+        (Self::MIN_DPI..=Self::MAX_DPI).contains(&self.dpi) && Self::DEPTHS.contains(&self.depth)
+    }
+
+    /// Returns the scale factor of the display relative to the standard 96 DPI.
+    pub fn scale(&self) -> f64 {
+        self.dpi as f64 / 96.0
+    }
+}
+
+impl Default for DisplayProperties {
+    /// Creates the standard display properties: 96 DPI and a 24-bit color depth.
+    fn default() -> Self {
+        Self::new(96, 24)
+    }
+}
+
+impl fmt::Display for DisplayProperties {
+    /// Formats the `DisplayProperties` as a string in the format "dpi DPI, depth bit".
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{} DPI, {} bit", self.dpi, self.depth)
+    }
+}
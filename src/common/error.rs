@@ -10,13 +10,16 @@ pub struct ApplicationError {
 }
 
 /// The `ApplicationErrorKind` enum categorizes the types of errors that can occur.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ApplicationErrorKind {
     Configuration,
     Authentication,
     Environment,
     Transport,
     Session,
+    LockedOut,
+    InvalidResolution,
+    Draining,
 }
 
 impl ApplicationError {
@@ -105,10 +108,57 @@ impl ApplicationError {
         )
     }
 
+    /// Creates a locked out error, returned when too many login attempts have been made.
+    ///
+    /// # Arguments
+    /// * `explanation` - The explanation for the error.
+    ///
+    /// # Returns
+    /// An `ApplicationError` instance.
+    pub fn locked_out(explanation: impl AsRef<str>) -> Self {
+        Self::new(
+            explanation,
+            ApplicationErrorKind::LockedOut,
+        )
+    }
+
+    /// Creates an invalid resolution error, returned when a requested screen resolution is rejected.
+    ///
+    /// # Arguments
+    /// * `explanation` - The explanation for the error.
+    ///
+    /// # Returns
+    /// An `ApplicationError` instance.
+    pub fn invalid_resolution(explanation: impl AsRef<str>) -> Self {
+        Self::new(
+            explanation,
+            ApplicationErrorKind::InvalidResolution,
+        )
+    }
+
+    /// Creates a draining error, returned when a login is rejected because the host is being drained.
+    ///
+    /// # Arguments
+    /// * `explanation` - The explanation for the error.
+    ///
+    /// # Returns
+    /// An `ApplicationError` instance.
+    pub fn draining(explanation: impl AsRef<str>) -> Self {
+        Self::new(
+            explanation,
+            ApplicationErrorKind::Draining,
+        )
+    }
+
     /// Returns the error message.
     pub fn message(&self) -> &str {
         &self.message
     }
+
+    /// Returns the kind of error.
+    pub fn kind(&self) -> ApplicationErrorKind {
+        self.kind
+    }
 }
 
 impl fmt::Display for ApplicationError {
@@ -126,7 +176,10 @@ impl fmt::Display for ApplicationErrorKind {
             ApplicationErrorKind::Environment => "invalid environment",
             ApplicationErrorKind::Transport => "transport",
             ApplicationErrorKind::Session => "issue launching session",
-            ApplicationErrorKind::Configuration => "configuration"
+            ApplicationErrorKind::Configuration => "configuration",
+            ApplicationErrorKind::LockedOut => "too many login attempts",
+            ApplicationErrorKind::InvalidResolution => "invalid screen resolution",
+            ApplicationErrorKind::Draining => "the host is being drained"
         };
         write!(f, "{}", string)
     }
@@ -0,0 +1,67 @@
+/// The `Grant` struct represents the access to a session granted to another user, for example so that
+/// support staff can look at the desktop of the session owner. The grantee is given an Xauthority file of its
+/// own, whose cookie is generated by the display server and revoked when the grant is revoked.
+#[derive(Clone)]
+pub struct Grant {
+    grantee: String,
+    view_only: bool,
+    xauthority_file_path: String,
+    cookie: String,
+    authorization_id: u32,
+    created: i64,
+}
+
+impl Grant {
+    /// Creates a new `Grant` instance.
+    ///
+    /// # Arguments
+    /// * `grantee` - The username of the user granted access.
+    /// * `view_only` - Whether the grantee may only view the session, without sending input.
+    /// * `xauthority_file_path` - The path to the Xauthority file of the grantee.
+    /// * `cookie` - The hexadecimal cookie of the Xauthority file.
+    /// * `authorization_id` - The identifier of the authorization of the cookie in the display server.
+    /// * `created` - The unix timestamp at which access was granted.
+    ///
+    /// # Returns
+    /// A new `Grant` instance.
+    pub fn new(grantee: String, view_only: bool, xauthority_file_path: String, cookie: String, authorization_id: u32, created: i64) -> Self {
+        Self {
+            grantee,
+            view_only,
+            xauthority_file_path,
+            cookie,
+            authorization_id,
+            created,
+        }
+    }
+
+    /// Returns the username of the user granted access.
+    pub fn grantee(&self) -> &str {
+        &self.grantee
+    }
+
+    /// Returns whether the grantee may only view the session.
+    pub fn is_view_only(&self) -> bool {
+        self.view_only
+    }
+
+    /// Returns the path to the Xauthority file of the grantee.
+    pub fn xauthority_file_path(&self) -> &str {
+        &self.xauthority_file_path
+    }
+
+    /// Returns the hexadecimal cookie of the Xauthority file of the grantee.
+    pub fn cookie(&self) -> &str {
+        &self.cookie
+    }
+
+    /// Returns the identifier of the authorization of the cookie in the display server.
+    pub fn authorization_id(&self) -> u32 {
+        self.authorization_id
+    }
+
+    /// Returns the unix timestamp at which access was granted.
+    pub fn created(&self) -> i64 {
+        self.created
+    }
+}
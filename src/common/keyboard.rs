@@ -0,0 +1,58 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The `KeyboardLayout` struct represents the XKB keyboard of a session: an optional model, the layout
+/// and an optional variant. Several layouts (and their variants) can be given as comma-separated lists.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct KeyboardLayout {
+    #[serde(default)]
+    model: Option<String>,
+    layout: String,
+    #[serde(default)]
+    variant: Option<String>,
+}
+
+impl KeyboardLayout {
+    /// Creates a new `KeyboardLayout` instance.
+    ///
+    /// # Arguments
+    /// * `model` - The XKB model (for example `pc105`), or `None` for the default.
+    /// * `layout` - The XKB layout (for example `us` or `fr,us`).
+    /// * `variant` - The XKB variant (for example `intl`), or `None` for the default.
+    ///
+    /// # Returns
+    /// A new `KeyboardLayout` instance.
+    pub fn new(model: Option<String>, layout: String, variant: Option<String>) -> Self {
+        Self {
+            model,
+            layout,
+            variant,
+        }
+    }
+
+    /// Returns the XKB model, if any.
+    pub fn model(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    /// Returns the XKB layout.
+    pub fn layout(&self) -> &str {
+        &self.layout
+    }
+
+    /// Returns the XKB variant, if any.
+    pub fn variant(&self) -> Option<&str> {
+        self.variant.as_deref()
+    }
+}
+
+impl fmt::Display for KeyboardLayout {
+    /// Formats the `KeyboardLayout` as a string in the format "layout(variant)".
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.variant {
+            Some(variant) => write!(formatter, "{}({})", self.layout, variant),
+            None => write!(formatter, "{}", self.layout),
+        }
+    }
+}
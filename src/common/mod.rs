@@ -1,15 +1,31 @@
 pub use account::Account;
-pub use error::ApplicationError;
+pub use desktop::Desktop;
+pub use display_properties::DisplayProperties;
+pub use error::{ApplicationError, ApplicationErrorKind};
+pub use grant::Grant;
+pub use keyboard::KeyboardLayout;
+pub use monitor::Monitor;
+pub use notification::{Notification, NotificationUrgency};
 pub use process::ProcessHandle;
 pub use session::Session;
-pub use settings::{AuthenticationSettings, LoggingSettings, Settings, TransportSettings, XorgSettings};
+pub use session_filter::{SessionFilter, SessionSort, SessionState};
+pub use session_options::SessionOptions;
+pub use settings::{AccessSettings, AuthenticationSettings, DisplayServerKind, DrainSettings, LoggingSettings, LoginWindowPolicy, LoginWindowSettings, ProfileExitPolicy, ProfileSettings, RateLimitSettings, ReconnectionSettings, ResolutionSettings, ResourceLimitSettings, RevalidationAction, RevalidationSettings, Settings, TicketKeySettings, TicketSettings, TotpSettings, TransportSettings, XorgSettings};
 pub use transport::{Encoder, Request, Response};
 pub use resolution::ScreenResolution;
 
 mod account;
+mod desktop;
+mod display_properties;
 mod settings;
 mod error;
+mod grant;
+mod keyboard;
+mod monitor;
+mod notification;
 mod session;
+mod session_filter;
+mod session_options;
 mod transport;
 mod process;
 mod resolution;
\ No newline at end of file
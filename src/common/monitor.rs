@@ -0,0 +1,108 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::ApplicationError;
+
+/// The `Monitor` struct represents one monitor of a multi-monitor screen layout: its position
+/// within the screen, its size and whether it is the primary monitor.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Monitor {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    #[serde(default)]
+    primary: bool,
+}
+
+impl Monitor {
+    /// Creates a new `Monitor` instance.
+    ///
+    /// # Arguments
+    /// * `x` - The horizontal position of the monitor within the screen, in pixels.
+    /// * `y` - The vertical position of the monitor within the screen, in pixels.
+    /// * `width` - The width of the monitor in pixels.
+    /// * `height` - The height of the monitor in pixels.
+    /// * `primary` - Whether the monitor is the primary monitor.
+    ///
+    /// # Returns
+    /// A new `Monitor` instance.
+    pub fn new(x: u32, y: u32, width: u32, height: u32, primary: bool) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            primary,
+        }
+    }
+
+    /// Returns the horizontal position of the monitor within the screen.
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    /// Returns the vertical position of the monitor within the screen.
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+
+    /// Returns the width of the monitor in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the monitor in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns whether the monitor is the primary monitor.
+    pub fn is_primary(&self) -> bool {
+        self.primary
+    }
+
+    /// Returns the horizontal position of the right edge of the monitor.
+    pub fn right(&self) -> u32 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// Returns the vertical position of the bottom edge of the monitor.
+    pub fn bottom(&self) -> u32 {
+        self.y.saturating_add(self.height)
+    }
+}
+
+impl fmt::Display for Monitor {
+    /// Formats the `Monitor` as a string in the format "widthxheight+x+y", followed by "*" for the primary monitor.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}x{}+{}+{}{}", self.width, self.height, self.x, self.y, if self.primary { "*" } else { "" })
+    }
+}
+
+impl FromStr for Monitor {
+    type Err = ApplicationError;
+
+    /// Parses a `Monitor` from a string in the format "widthxheight+x+y", followed by "*" for the primary monitor.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || ApplicationError::session(format!("Invalid monitor {}: expected widthxheight+x+y", value));
+
+        let (geometry, primary) = match value.strip_suffix('*') {
+            Some(geometry) => (geometry, true),
+            None => (value, false),
+        };
+        let mut parts = geometry.split('+');
+        let size = parts.next().ok_or_else(invalid)?;
+        let x = parts.next().ok_or_else(invalid)?;
+        let y = parts.next().ok_or_else(invalid)?;
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        let (width, height) = size.split_once('x').ok_or_else(invalid)?;
+
+        let parse = |value: &str| value.trim().parse::<u32>().map_err(|_| invalid());
+        Ok(Monitor::new(parse(x)?, parse(y)?, parse(width)?, parse(height)?, primary))
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::ApplicationError;
+
+/// The `NotificationUrgency` enum represents the urgency of a desktop notification, as defined by the
+/// desktop notifications specification.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationUrgency {
+    Low,
+    #[default]
+    Normal,
+    /// Critical notifications are not closed automatically by most notification daemons.
+    Critical,
+}
+
+impl fmt::Display for NotificationUrgency {
+    /// Formats the `NotificationUrgency` as its name in the requests, which is also the one used by `notify-send`.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotificationUrgency::Low => write!(formatter, "low"),
+            NotificationUrgency::Normal => write!(formatter, "normal"),
+            NotificationUrgency::Critical => write!(formatter, "critical"),
+        }
+    }
+}
+
+impl FromStr for NotificationUrgency {
+    type Err = ApplicationError;
+
+    /// Parses a `NotificationUrgency` from its name in the requests.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "low" => Ok(NotificationUrgency::Low),
+            "normal" => Ok(NotificationUrgency::Normal),
+            "critical" => Ok(NotificationUrgency::Critical),
+            _ => Err(ApplicationError::session(format!("Unknown notification urgency {}", name))),
+        }
+    }
+}
+
+/// The `Notification` struct represents a desktop notification shown to the users of sessions.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    summary: String,
+    body: Option<String>,
+    urgency: NotificationUrgency,
+}
+
+impl Notification {
+    /// The maximum length of the summary and body of a notification.
+    pub const MAX_LENGTH: usize = 1024;
+
+    /// Creates a new `Notification` instance.
+    ///
+    /// # Arguments
+    /// * `summary` - The summary (title) of the notification.
+    /// * `body` - The body of the notification, if any.
+    /// * `urgency` - The urgency of the notification.
+    ///
+    /// # Returns
+    /// A new `Notification` instance.
+    pub fn new(summary: String, body: Option<String>, urgency: NotificationUrgency) -> Self {
+        Self {
+            summary,
+            body,
+            urgency,
+        }
+    }
+
+    /// Returns the summary (title) of the notification.
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    /// Returns the body of the notification, if any.
+    pub fn body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    /// Returns the urgency of the notification.
+    pub fn urgency(&self) -> NotificationUrgency {
+        self.urgency
+    }
+
+    /// Verifies that the notification has a summary and that its summary and body are not too long.
+    ///
+    /// # Returns
+    /// A `Result` indicating the notification is valid or an `ApplicationError`.
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        if self.summary.trim().is_empty() {
+            return Err(ApplicationError::session("The summary of the notification is empty"));
+        }
+        if self.summary.len() > Self::MAX_LENGTH || self.body.as_ref().is_some_and(|body| body.len() > Self::MAX_LENGTH) {
+            return Err(ApplicationError::session(format!("The summary and body of the notification cannot exceed {} bytes", Self::MAX_LENGTH)));
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,15 @@
 use std::fmt;
+use std::str::FromStr;
 
-/// The `ScreenResolution` struct represents the screen resolution for a session.
-#[derive(Clone)]
+use crate::common::{ApplicationError, Monitor};
+
+/// The `ScreenResolution` struct represents the screen resolution for a session and, for multi-monitor
+/// sessions, the layout of the monitors within the screen.
+#[derive(Clone, PartialEq)]
 pub struct ScreenResolution {
     width: u32,
-    height: u32
+    height: u32,
+    monitors: Vec<Monitor>,
 }
 
 impl ScreenResolution {
@@ -19,8 +24,25 @@ impl ScreenResolution {
     pub fn new(width: u32, height: u32) -> Self {
         Self {
             width,
-            height
+            height,
+            monitors: vec![],
+        }
+    }
+
+    /// Sets the monitor layout of the screen. The screen resolution becomes the bounding box of the monitors.
+    ///
+    /// # Arguments
+    /// * `monitors` - The monitors of the layout. The resolution is unchanged if it is empty.
+    ///
+    /// # Returns
+    /// The `ScreenResolution` including the monitor layout.
+    pub fn with_monitors(mut self, monitors: Vec<Monitor>) -> Self {
+        if !monitors.is_empty() {
+            self.width = monitors.iter().map(Monitor::right).max().unwrap_or(self.width);
+            self.height = monitors.iter().map(Monitor::bottom).max().unwrap_or(self.height);
+            self.monitors = monitors;
         }
+        self
     }
 
     /// Returns the width of the screen in pixels.
@@ -40,6 +62,11 @@ impl ScreenResolution {
     pub fn split(&self) -> (u32, u32) {
         (self.width, self.height)
     }
+
+    /// Returns the monitors of the layout, empty for a single-monitor screen.
+    pub fn monitors(&self) -> &[Monitor] {
+        &self.monitors
+    }
 }
 
 impl fmt::Display for ScreenResolution {
@@ -48,3 +75,16 @@ impl fmt::Display for ScreenResolution {
         write!(formatter, "{}x{}", self.width, self.height)
     }
 }
+
+impl FromStr for ScreenResolution {
+    type Err = ApplicationError;
+
+    /// Parses a `ScreenResolution` from a string in the format "widthxheight".
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || ApplicationError::invalid_resolution(format!("Invalid resolution {}: expected widthxheight", value));
+        let (width, height) = value.split_once('x').ok_or_else(invalid)?;
+        let width = width.trim().parse::<u32>().map_err(|_| invalid())?;
+        let height = height.trim().parse::<u32>().map_err(|_| invalid())?;
+        Ok(ScreenResolution::new(width, height))
+    }
+}
@@ -1,10 +1,11 @@
+use std::collections::HashMap;
 use std::fmt;
 
 use uuid::Uuid;
 
 use crate::common::ProcessHandle;
 
-use super::ScreenResolution;
+use super::{DisplayProperties, DisplayServerKind, Grant, ScreenResolution};
 
 /// The `Session` struct represents a user session managed by the WebX Session Manager.
 /// It contains details about the session, such as the user, session ID, the Xorg process and the Window Manager process.
@@ -15,9 +16,22 @@ pub struct Session {
     uid: u32,
     display_id: String,
     xauthority_file_path: String,
+    server_xauthority_file_path: String,
+    server_cookie: String,
+    cookie: String,
+    authorization_id: Option<u32>,
+    grants: Vec<Grant>,
     xorg: ProcessHandle,
     window_manager: ProcessHandle,
     resolution: ScreenResolution,
+    display_server: DisplayServerKind,
+    display_properties: DisplayProperties,
+    profile: Option<String>,
+    labels: HashMap<String, String>,
+    created: i64,
+    end_on_exit: bool,
+    expires: Option<i64>,
+    expiry_warning: u64,
 }
 
 #[allow(dead_code)]
@@ -33,6 +47,7 @@ impl Session {
     /// * `xorg` - The process handle for the Xorg server.
     /// * `window_manager` - The process handle for the window manager.
     /// * `resolution` - The screen resolution for the session.
+    /// * `display_server` - The display server backend of the session.
     ///
     /// # Returns
     /// A new `Session` instance.
@@ -46,16 +61,30 @@ impl Session {
         xorg: ProcessHandle,
         window_manager: ProcessHandle,
         resolution: ScreenResolution,
+        display_server: DisplayServerKind,
     ) -> Self {
         Self {
             id,
             username,
             uid,
             display_id,
+            server_xauthority_file_path: xauthority_file_path.clone(),
+            server_cookie: String::new(),
+            cookie: String::new(),
+            authorization_id: None,
+            grants: vec![],
             xauthority_file_path,
             xorg,
             window_manager,
             resolution,
+            display_server,
+            display_properties: DisplayProperties::default(),
+            profile: None,
+            labels: HashMap::new(),
+            created: chrono::Utc::now().timestamp(),
+            end_on_exit: false,
+            expires: None,
+            expiry_warning: 0,
         }
     }
 
@@ -84,6 +113,82 @@ impl Session {
         &self.xauthority_file_path
     }
 
+    /// Sets the Xauthority file given to the display server and used by the window manager of the session,
+    /// whose cookie cannot be revoked while the display server is running.
+    ///
+    /// # Arguments
+    /// * `server_xauthority_file_path` - The path to the Xauthority file of the display server.
+    /// * `server_cookie` - The hexadecimal cookie of the file.
+    ///
+    /// # Returns
+    /// The `Session` including the Xauthority file of the display server.
+    pub fn with_server_authority(mut self, server_xauthority_file_path: String, server_cookie: String) -> Self {
+        self.server_xauthority_file_path = server_xauthority_file_path;
+        self.server_cookie = server_cookie;
+        self
+    }
+
+    /// Returns the path to the Xauthority file of the display server.
+    pub fn server_xauthority_file_path(&self) -> &str {
+        &self.server_xauthority_file_path
+    }
+
+    /// Returns the hexadecimal cookie of the Xauthority file of the display server.
+    pub fn server_cookie(&self) -> &str {
+        &self.server_cookie
+    }
+
+    /// Sets the cookie of the Xauthority file given to the clients of the session.
+    ///
+    /// # Arguments
+    /// * `cookie` - The hexadecimal cookie.
+    /// * `authorization_id` - The identifier of the authorization generated by the display server, or `None`
+    ///   if the cookie is the one of the display server (and cannot be revoked).
+    pub fn set_cookie(&mut self, cookie: String, authorization_id: Option<u32>) {
+        self.cookie = cookie;
+        self.authorization_id = authorization_id;
+    }
+
+    /// Returns the hexadecimal cookie of the Xauthority file given to the clients of the session.
+    pub fn cookie(&self) -> &str {
+        &self.cookie
+    }
+
+    /// Returns the identifier of the revocable authorization of the cookie, if any.
+    pub fn authorization_id(&self) -> Option<u32> {
+        self.authorization_id
+    }
+
+    /// Records the access to the session granted to another user.
+    ///
+    /// # Arguments
+    /// * `grant` - The grant.
+    pub fn add_grant(&mut self, grant: Grant) {
+        self.grants.push(grant);
+    }
+
+    /// Removes the access to the session granted to a user.
+    ///
+    /// # Arguments
+    /// * `grantee` - The username of the user granted access.
+    ///
+    /// # Returns
+    /// An `Option` containing the removed `Grant`, or `None` if the user was not granted access.
+    pub fn remove_grant(&mut self, grantee: &str) -> Option<Grant> {
+        let index = self.grants.iter().position(|grant| grant.grantee() == grantee)?;
+        Some(self.grants.remove(index))
+    }
+
+    /// Returns the access to the session granted to other users.
+    pub fn grants(&self) -> &[Grant] {
+        &self.grants
+    }
+
+    /// Returns the unix timestamp at which the session was created.
+    pub fn created(&self) -> i64 {
+        self.created
+    }
+
     /// Returns the process handle for the Xorg server.
     pub fn xorg(&self) -> &ProcessHandle {
         &self.xorg
@@ -98,6 +203,101 @@ impl Session {
     pub fn resolution(&self) -> &ScreenResolution {
         &self.resolution
     }
+
+    /// Sets the screen resolution of the session, once the display has been resized.
+    ///
+    /// # Arguments
+    /// * `resolution` - The new screen resolution.
+    pub fn set_resolution(&mut self, resolution: ScreenResolution) {
+        self.resolution = resolution;
+    }
+
+    /// Sets the profile of the session.
+    ///
+    /// # Arguments
+    /// * `profile` - The name of the session profile.
+    /// * `end_on_exit` - Whether the session ends when its window manager (or kiosk application) exits.
+    ///
+    /// # Returns
+    /// The `Session` including the profile.
+    pub fn with_profile(mut self, profile: &str, end_on_exit: bool) -> Self {
+        self.profile = Some(profile.into());
+        self.end_on_exit = end_on_exit;
+        self
+    }
+
+    /// Sets the DPI and color depth of the display of the session.
+    ///
+    /// # Arguments
+    /// * `display_properties` - The properties of the display.
+    ///
+    /// # Returns
+    /// The `Session` including the display properties.
+    pub fn with_display_properties(mut self, display_properties: DisplayProperties) -> Self {
+        self.display_properties = display_properties;
+        self
+    }
+
+    /// Returns the DPI and color depth of the display of the session.
+    pub fn display_properties(&self) -> DisplayProperties {
+        self.display_properties
+    }
+
+    /// Sets the labels of the session, the metadata provided by the client at login.
+    ///
+    /// # Arguments
+    /// * `labels` - The keys and values of the labels.
+    ///
+    /// # Returns
+    /// The `Session` including the labels.
+    pub fn with_labels(mut self, labels: HashMap<String, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Returns the labels of the session.
+    pub fn labels(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    /// Sets the time at which the session reaches its maximum duration.
+    ///
+    /// # Arguments
+    /// * `expires` - The unix timestamp after which the session is terminated.
+    /// * `warning` - The number of minutes before the expiry at which the user is warned.
+    ///
+    /// # Returns
+    /// The `Session` including the expiry.
+    pub fn with_expiry(mut self, expires: i64, warning: u64) -> Self {
+        self.expires = Some(expires);
+        self.expiry_warning = warning;
+        self
+    }
+
+    /// Returns the unix timestamp after which the session is terminated, if it has a maximum duration.
+    pub fn expires(&self) -> Option<i64> {
+        self.expires
+    }
+
+    /// Returns the number of minutes before the expiry at which the user is warned.
+    pub fn expiry_warning(&self) -> u64 {
+        self.expiry_warning
+    }
+
+    /// Returns the name of the session profile, if any.
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Returns whether the session ends when its window manager (or kiosk application) exits.
+    pub fn end_on_exit(&self) -> bool {
+        self.end_on_exit
+    }
+
+    /// Returns the display server backend of the session.
+    pub fn display_server(&self) -> DisplayServerKind {
+        self.display_server
+    }
 }
 
 impl fmt::Display for Session {
@@ -109,6 +309,9 @@ impl fmt::Display for Session {
             .field("display_id", &self.display_id)
             .field("xauthority_file_path", &self.xauthority_file_path)
             .field("resolution", &format!("{}", &self.resolution))
+            .field("display_server", &format!("{}", &self.display_server))
+            .field("profile", &self.profile)
+            .field("labels", &self.labels)
             .field("xorg pid", &self.xorg.pid())
             .field("window_manager pid", &self.window_manager.pid())
             .finish()
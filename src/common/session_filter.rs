@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{ApplicationError, Session};
+
+/// The `SessionState` enum represents the state of a session as reported by the who request.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionState {
+    /// The session is running and its user qualifies for it.
+    Active,
+    /// The user of the session no longer qualifies for it (see the revalidation of the session users).
+    Flagged,
+}
+
+impl fmt::Display for SessionState {
+    /// Formats the `SessionState` as its name in the requests.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionState::Active => write!(formatter, "active"),
+            SessionState::Flagged => write!(formatter, "flagged"),
+        }
+    }
+}
+
+impl FromStr for SessionState {
+    type Err = ApplicationError;
+
+    /// Parses a `SessionState` from its name in the requests.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "active" => Ok(SessionState::Active),
+            "flagged" => Ok(SessionState::Flagged),
+            _ => Err(ApplicationError::session(format!("Unknown session state {}", name))),
+        }
+    }
+}
+
+/// The `SessionSort` enum determines the order of the sessions listed by the who request.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionSort {
+    /// The sessions are sorted by creation time.
+    Created,
+    /// The sessions are sorted by username.
+    Username,
+    /// The sessions are sorted by user ID.
+    Uid,
+    /// The sessions are sorted by display number.
+    Display,
+}
+
+impl FromStr for SessionSort {
+    type Err = ApplicationError;
+
+    /// Parses a `SessionSort` from its name in the requests.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "created" => Ok(SessionSort::Created),
+            "username" => Ok(SessionSort::Username),
+            "uid" => Ok(SessionSort::Uid),
+            "display" => Ok(SessionSort::Display),
+            _ => Err(ApplicationError::session(format!("Unknown session sort {}", name))),
+        }
+    }
+}
+
+/// The `SessionFilter` struct contains the criteria restricting the sessions listed by a who request, and their order.
+/// A session is listed if it matches all the criteria that are set.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SessionFilter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    uid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    state: Option<SessionState>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    labels: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    display: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_before: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_after: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sort: Option<SessionSort>,
+    #[serde(default)]
+    descending: bool,
+}
+
+impl SessionFilter {
+    /// Restricts the sessions to those of a user.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the session owner, or `None` for all users.
+    ///
+    /// # Returns
+    /// The `SessionFilter` including the username.
+    pub fn with_username(mut self, username: Option<String>) -> Self {
+        self.username = username;
+        self
+    }
+
+    /// Restricts the sessions to those of a user ID.
+    ///
+    /// # Arguments
+    /// * `uid` - The user ID of the session owner, or `None` for all users.
+    ///
+    /// # Returns
+    /// The `SessionFilter` including the user ID.
+    pub fn with_uid(mut self, uid: Option<u32>) -> Self {
+        self.uid = uid;
+        self
+    }
+
+    /// Restricts the sessions to those in a state.
+    ///
+    /// # Arguments
+    /// * `state` - The state of the sessions, or `None` for all states.
+    ///
+    /// # Returns
+    /// The `SessionFilter` including the state.
+    pub fn with_state(mut self, state: Option<SessionState>) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Restricts the sessions to those having labels.
+    ///
+    /// # Arguments
+    /// * `labels` - The keys and values of the labels that the sessions must have.
+    ///
+    /// # Returns
+    /// The `SessionFilter` including the labels.
+    pub fn with_labels(mut self, labels: HashMap<String, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Restricts the sessions to the one running on a display.
+    ///
+    /// # Arguments
+    /// * `display` - The display (for example `:60` or `60`), or `None` for all displays.
+    ///
+    /// # Returns
+    /// The `SessionFilter` including the display.
+    pub fn with_display(mut self, display: Option<String>) -> Self {
+        self.display = display;
+        self
+    }
+
+    /// Restricts the sessions to those created within a period.
+    ///
+    /// # Arguments
+    /// * `created_after` - The unix timestamp after which the sessions were created, if any.
+    /// * `created_before` - The unix timestamp before which the sessions were created, if any.
+    ///
+    /// # Returns
+    /// The `SessionFilter` including the period.
+    pub fn with_created(mut self, created_after: Option<i64>, created_before: Option<i64>) -> Self {
+        self.created_after = created_after;
+        self.created_before = created_before;
+        self
+    }
+
+    /// Sets the order of the sessions.
+    ///
+    /// # Arguments
+    /// * `sort` - The key by which the sessions are sorted, or `None` to keep the order of creation.
+    /// * `descending` - Whether the sessions are sorted in descending order.
+    ///
+    /// # Returns
+    /// The `SessionFilter` including the order.
+    pub fn with_sort(mut self, sort: Option<SessionSort>, descending: bool) -> Self {
+        self.sort = sort;
+        self.descending = descending;
+        self
+    }
+
+    /// Determines whether the filter has no criteria, in which case it matches all the sessions.
+    ///
+    /// # Returns
+    /// `true` if none of the criteria are set, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.username.is_none()
+            && self.uid.is_none()
+            && self.state.is_none()
+            && self.labels.is_empty()
+            && self.display.is_none()
+            && self.created_after.is_none()
+            && self.created_before.is_none()
+    }
+
+    /// Returns the labels that the sessions must have.
+    pub fn labels(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    /// Determines whether a session matches the criteria of the filter.
+    ///
+    /// # Arguments
+    /// * `session` - The session.
+    /// * `state` - The state of the session.
+    ///
+    /// # Returns
+    /// `true` if the session matches all the criteria, otherwise `false`.
+    pub fn matches(&self, session: &Session, state: SessionState) -> bool {
+        self.username.as_ref().is_none_or(|username| username == session.username())
+            && self.uid.is_none_or(|uid| uid == session.uid())
+            && self.state.is_none_or(|expected| expected == state)
+            && self.display.as_ref().is_none_or(|display| display.trim_start_matches(':') == session.display_id().trim_start_matches(':'))
+            && self.created_after.is_none_or(|created_after| session.created() > created_after)
+            && self.created_before.is_none_or(|created_before| session.created() < created_before)
+            && self.labels.iter().all(|(key, value)| session.labels().get(key) == Some(value))
+    }
+
+    /// Sorts sessions in the order of the filter.
+    ///
+    /// # Arguments
+    /// * `sessions` - The sessions to sort.
+    pub fn sort(&self, sessions: &mut [Session]) {
+        let sort = match self.sort {
+            Some(sort) => sort,
+            None => return,
+        };
+        sessions.sort_by(|a, b| {
+            let ordering = match sort {
+                SessionSort::Created => a.created().cmp(&b.created()),
+                SessionSort::Username => a.username().cmp(b.username()),
+                SessionSort::Uid => a.uid().cmp(&b.uid()),
+                SessionSort::Display => display_number(a).cmp(&display_number(b)),
+            };
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+}
+
+/// Returns the number of the display of a session, so that `:100` is sorted after `:99`.
+fn display_number(session: &Session) -> Option<u32> {
+    session.display_id().trim_start_matches(':').parse().ok()
+}
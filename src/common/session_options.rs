@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+
+use crate::common::KeyboardLayout;
+
+/// The `SessionOptions` struct contains the optional choices made by a client when requesting a new session,
+/// such as the display server backend, the desktop environment, the session profile, the display properties,
+/// the keyboard, locale and timezone of the user, extra environment variables and labels.
+#[derive(Clone, Default)]
+pub struct SessionOptions {
+    display_server: Option<String>,
+    desktop: Option<String>,
+    profile: Option<String>,
+    dpi: Option<u32>,
+    depth: Option<u32>,
+    keyboard: Option<KeyboardLayout>,
+    locale: Option<String>,
+    timezone: Option<String>,
+    environment: HashMap<String, String>,
+    labels: HashMap<String, String>,
+}
+
+impl SessionOptions {
+    /// Sets the display server backend of the session.
+    ///
+    /// # Arguments
+    /// * `display_server` - The name of the display server backend, or `None` for the default.
+    ///
+    /// # Returns
+    /// The `SessionOptions` including the display server.
+    pub fn with_display_server(mut self, display_server: Option<String>) -> Self {
+        self.display_server = display_server;
+        self
+    }
+
+    /// Sets the desktop environment of the session.
+    ///
+    /// # Arguments
+    /// * `desktop` - The id of the xsessions desktop entry, or `None` for the default window manager.
+    ///
+    /// # Returns
+    /// The `SessionOptions` including the desktop.
+    pub fn with_desktop(mut self, desktop: Option<String>) -> Self {
+        self.desktop = desktop;
+        self
+    }
+
+    /// Sets the profile of the session.
+    ///
+    /// # Arguments
+    /// * `profile` - The name of the session profile, or `None` for none.
+    ///
+    /// # Returns
+    /// The `SessionOptions` including the profile.
+    pub fn with_profile(mut self, profile: Option<String>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Sets the DPI of the display of the session.
+    ///
+    /// # Arguments
+    /// * `dpi` - The resolution of the display in dots per inch, or `None` for the default.
+    ///
+    /// # Returns
+    /// The `SessionOptions` including the DPI.
+    pub fn with_dpi(mut self, dpi: Option<u32>) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /// Sets the color depth of the display of the session.
+    ///
+    /// # Arguments
+    /// * `depth` - The color depth in bits per pixel, or `None` for the default.
+    ///
+    /// # Returns
+    /// The `SessionOptions` including the color depth.
+    pub fn with_depth(mut self, depth: Option<u32>) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the keyboard of the session.
+    ///
+    /// # Arguments
+    /// * `keyboard` - The XKB keyboard layout, or `None` for the default of the display server.
+    ///
+    /// # Returns
+    /// The `SessionOptions` including the keyboard.
+    pub fn with_keyboard(mut self, keyboard: Option<KeyboardLayout>) -> Self {
+        self.keyboard = keyboard;
+        self
+    }
+
+    /// Sets the locale of the session.
+    ///
+    /// # Arguments
+    /// * `locale` - The locale (for example `fr_CH.UTF-8`), or `None` for the default of the host.
+    ///
+    /// # Returns
+    /// The `SessionOptions` including the locale.
+    pub fn with_locale(mut self, locale: Option<String>) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Sets the timezone of the session.
+    ///
+    /// # Arguments
+    /// * `timezone` - The timezone (for example `Europe/Paris`), or `None` for the default of the host.
+    ///
+    /// # Returns
+    /// The `SessionOptions` including the timezone.
+    pub fn with_timezone(mut self, timezone: Option<String>) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Sets the environment variables provided by the client.
+    ///
+    /// # Arguments
+    /// * `environment` - The names and values of the environment variables, only those permitted by the settings being applied.
+    ///
+    /// # Returns
+    /// The `SessionOptions` including the environment variables.
+    pub fn with_environment(mut self, environment: HashMap<String, String>) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Sets the labels of the session.
+    ///
+    /// # Arguments
+    /// * `labels` - The keys and values of the labels, such as the portal instance or the client address.
+    ///
+    /// # Returns
+    /// The `SessionOptions` including the labels.
+    pub fn with_labels(mut self, labels: HashMap<String, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Returns the name of the requested display server backend, if any.
+    pub fn display_server(&self) -> Option<&str> {
+        self.display_server.as_deref()
+    }
+
+    /// Returns the id of the requested desktop, if any.
+    pub fn desktop(&self) -> Option<&str> {
+        self.desktop.as_deref()
+    }
+
+    /// Returns the name of the requested profile, if any.
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Returns the requested DPI, if any.
+    pub fn dpi(&self) -> Option<u32> {
+        self.dpi
+    }
+
+    /// Returns the requested color depth, if any.
+    pub fn depth(&self) -> Option<u32> {
+        self.depth
+    }
+
+    /// Returns the requested keyboard, if any.
+    pub fn keyboard(&self) -> Option<&KeyboardLayout> {
+        self.keyboard.as_ref()
+    }
+
+    /// Returns the requested locale, if any.
+    pub fn locale(&self) -> Option<&str> {
+        self.locale.as_deref()
+    }
+
+    /// Returns the requested timezone, if any.
+    pub fn timezone(&self) -> Option<&str> {
+        self.timezone.as_deref()
+    }
+
+    /// Returns the environment variables provided by the client.
+    pub fn environment(&self) -> &HashMap<String, String> {
+        &self.environment
+    }
+
+    /// Returns the labels of the session.
+    pub fn labels(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+}
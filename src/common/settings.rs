@@ -1,8 +1,9 @@
-use std::{path::Path};
+use std::{collections::HashMap, fmt, path::Path, str::FromStr};
 
-use serde::Deserialize;
+use chrono::{NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
 
-use super::ApplicationError;
+use super::{ApplicationError, DisplayProperties, ScreenResolution};
 
 /// The `Settings` struct represents the configuration settings for the WebX Session Manager.
 #[derive(Debug, Deserialize, Clone)]
@@ -11,12 +12,18 @@ pub struct Settings {
     authentication: AuthenticationSettings,
     transport: TransportSettings,
     xorg: XorgSettings,
+    access: Option<AccessSettings>,
+    revalidation: Option<RevalidationSettings>,
+    drain: Option<DrainSettings>,
+    profiles: Option<Vec<ProfileSettings>>,
+    default_profile: Option<String>,
 }
 
 /// The `TransportSettings` struct contains settings related to IPC transport.
 #[derive(Debug, Deserialize, Clone)]
 pub struct TransportSettings {
     ipc: String,
+    admin_ipc: Option<String>,
 }
 
 /// The `XorgSettings` struct contains settings related to the Xorg server.
@@ -28,6 +35,187 @@ pub struct XorgSettings {
     config_path: String,
     display_offset: u32,
     window_manager: String,
+    display_server: Option<DisplayServerKind>,
+    display_servers: Option<Vec<DisplayServerKind>>,
+    xdummy_config_path: Option<String>,
+    xsessions_path: Option<String>,
+    desktops: Option<Vec<String>>,
+    kiosk_launcher: Option<String>,
+    resize_on_login: Option<bool>,
+    max_monitors: Option<usize>,
+    resolution: Option<ResolutionSettings>,
+    dpi: Option<u32>,
+    depth: Option<u32>,
+    xkb_rules_path: Option<String>,
+    zoneinfo_path: Option<String>,
+    client_environment: Option<Vec<String>>,
+    x11_socket_path: Option<String>,
+}
+
+/// The `ResolutionSettings` struct contains the limits applied to the screen resolutions requested by the clients.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ResolutionSettings {
+    min_width: Option<u32>,
+    min_height: Option<u32>,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+    max_pixels: Option<u64>,
+    multiple: Option<u32>,
+    modes: Option<Vec<String>>,
+}
+
+/// The `DisplayServerKind` enum identifies the display server backend launched for a session.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DisplayServerKind {
+    /// Xorg with the xorgxrdp driver.
+    Xorgxrdp,
+    /// The Xvfb virtual framebuffer server.
+    Xvfb,
+    /// Xorg with the dummy video driver.
+    Xdummy,
+    /// The TigerVNC Xvnc server.
+    Xvnc,
+}
+
+impl fmt::Display for DisplayServerKind {
+    /// Formats the `DisplayServerKind` as its name in the settings.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DisplayServerKind::Xorgxrdp => "xorgxrdp",
+            DisplayServerKind::Xvfb => "xvfb",
+            DisplayServerKind::Xdummy => "xdummy",
+            DisplayServerKind::Xvnc => "xvnc",
+        };
+        write!(formatter, "{}", name)
+    }
+}
+
+impl FromStr for DisplayServerKind {
+    type Err = ApplicationError;
+
+    /// Parses a `DisplayServerKind` from its name in the settings.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().as_str() {
+            "xorgxrdp" => Ok(DisplayServerKind::Xorgxrdp),
+            "xvfb" => Ok(DisplayServerKind::Xvfb),
+            "xdummy" => Ok(DisplayServerKind::Xdummy),
+            "xvnc" => Ok(DisplayServerKind::Xvnc),
+            _ => Err(ApplicationError::session(format!("Unknown display server {}", name))),
+        }
+    }
+}
+
+/// The `AccessSettings` struct contains the rules determining which users are permitted to start a desktop session.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct AccessSettings {
+    allowed_users: Option<Vec<String>>,
+    denied_users: Option<Vec<String>>,
+    allowed_groups: Option<Vec<String>>,
+    denied_groups: Option<Vec<String>>,
+    deny_root: Option<bool>,
+    minimum_uid: Option<u32>,
+    login_windows: Option<Vec<LoginWindowSettings>>,
+    terminate_outside_windows: Option<bool>,
+    termination_warning: Option<u64>,
+}
+
+/// The `ProfileExitPolicy` enum determines what happens when the application of a kiosk profile exits.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileExitPolicy {
+    /// The application is restarted.
+    Restart,
+    /// The session is ended.
+    Logout,
+}
+
+impl fmt::Display for ProfileExitPolicy {
+    /// Formats the `ProfileExitPolicy` as its name in the settings.
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileExitPolicy::Restart => write!(formatter, "restart"),
+            ProfileExitPolicy::Logout => write!(formatter, "logout"),
+        }
+    }
+}
+
+/// The `ProfileSettings` struct describes a session profile that can be chosen at login, overriding the global
+/// Xorg settings (display server, configuration, window manager) and adding environment variables, resource limits and timeouts.
+/// A profile with an application is a kiosk profile: the session only runs the application,
+/// optionally under a minimal window manager.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ProfileSettings {
+    name: String,
+    display_server: Option<DisplayServerKind>,
+    config_path: Option<String>,
+    application: Option<String>,
+    window_manager: Option<String>,
+    on_exit: Option<ProfileExitPolicy>,
+    groups: Option<Vec<String>>,
+    env: Option<HashMap<String, String>>,
+    limits: Option<ResourceLimitSettings>,
+    startup_wait: Option<u64>,
+    max_duration: Option<u64>,
+    max_duration_warning: Option<u64>,
+}
+
+/// The `ResourceLimitSettings` struct contains the resource limits applied to the processes of a session.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ResourceLimitSettings {
+    memory: Option<u64>,
+    processes: Option<u64>,
+    open_files: Option<u64>,
+    cpu_time: Option<u64>,
+}
+
+/// The `RevalidationAction` enum determines what happens to a session whose user no longer qualifies.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RevalidationAction {
+    /// The session is terminated.
+    Terminate,
+    /// The session is kept but flagged, and can no longer be reconnected to.
+    Flag,
+}
+
+/// The `RevalidationSettings` struct contains settings for the periodic revalidation of the users of running sessions.
+#[derive(Debug, Deserialize, Clone)]
+pub struct RevalidationSettings {
+    interval: Option<u64>,
+    action: Option<RevalidationAction>,
+}
+
+/// The `DrainSettings` struct contains settings for the drain mode, in which new logins are rejected so that the host
+/// can be emptied before a maintenance.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct DrainSettings {
+    state_path: Option<String>,
+    warning: Option<u64>,
+}
+
+/// The `LoginWindowPolicy` enum determines how a login window applies to the members of its groups.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LoginWindowPolicy {
+    /// Members of the groups may only log in during the window.
+    Restrict,
+    /// During the window, only members of the groups may log in.
+    Reserve,
+}
+
+/// The `LoginWindowSettings` struct describes the weekdays and hours during which a login window is open.
+/// A window whose end is before its start spans midnight.
+#[derive(Debug, Deserialize, Clone)]
+pub struct LoginWindowSettings {
+    name: Option<String>,
+    groups: Vec<String>,
+    days: Option<Vec<String>>,
+    start: String,
+    end: String,
+    policy: Option<LoginWindowPolicy>,
+    exempt_users: Option<Vec<String>>,
+    exempt_groups: Option<Vec<String>>,
 }
 
 /// The `FileLoggingSettings` struct contains settings for file-based logging.
@@ -44,18 +232,205 @@ pub struct LoggingSettings {
     console: Option<bool>,
     file: Option<FileLoggingSettings>,
     format: Option<String>,
+    audit: Option<FileLoggingSettings>,
 }
 
 /// The `AuthenticationSettings` struct contains settings for user authentication.
 #[derive(Debug, Deserialize, Clone)]
 pub struct AuthenticationSettings {
     service: String,
+    tickets: Option<TicketSettings>,
+    reconnection: Option<ReconnectionSettings>,
+    rate_limit: Option<RateLimitSettings>,
+    totp: Option<TotpSettings>,
+}
+
+/// The `TotpSettings` struct contains settings for the optional TOTP second factor managed by the session manager.
+#[derive(Debug, Deserialize, Clone)]
+pub struct TotpSettings {
+    store_path: Option<String>,
+    issuer: Option<String>,
+    required: Option<bool>,
+}
+
+/// The `TicketSettings` struct contains settings for verifying signed login tickets
+/// issued by a portal that has already authenticated the user.
+#[derive(Debug, Deserialize, Clone)]
+pub struct TicketSettings {
+    keys: Vec<TicketKeySettings>,
+    max_validity: Option<u64>,
+    nonce_store_path: Option<String>,
+}
+
+/// The `TicketKeySettings` struct contains a shared secret used to verify the HMAC-SHA256 signature of a ticket.
+#[derive(Debug, Deserialize, Clone)]
+pub struct TicketKeySettings {
+    id: String,
+    secret: String,
+}
+
+/// The `ReconnectionSettings` struct contains settings for the reconnection tokens returned with a session.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ReconnectionSettings {
+    ttl: Option<u64>,
+}
+
+/// The `RateLimitSettings` struct contains settings for throttling login attempts.
+#[derive(Debug, Deserialize, Clone)]
+pub struct RateLimitSettings {
+    max_attempts: Option<u32>,
+    window: Option<u64>,
+    lockout: Option<u64>,
+    max_lockout: Option<u64>,
+    global_max_attempts: Option<u32>,
+    global_window: Option<u64>,
 }
 
 impl AuthenticationSettings {
     pub fn service(&self) -> &str {
         &self.service
     }
+
+    pub fn tickets(&self) -> &Option<TicketSettings> {
+        &self.tickets
+    }
+
+    pub fn reconnection(&self) -> &Option<ReconnectionSettings> {
+        &self.reconnection
+    }
+
+    pub fn rate_limit(&self) -> &Option<RateLimitSettings> {
+        &self.rate_limit
+    }
+
+    pub fn totp(&self) -> &Option<TotpSettings> {
+        &self.totp
+    }
+}
+
+impl TotpSettings {
+    /// The path to the root-only file storing the TOTP secrets of the users.
+    pub fn store_path(&self) -> &str {
+        self.store_path.as_deref().unwrap_or("/etc/webx/webx-session-manager/totp.json")
+    }
+
+    /// The issuer shown in authenticator applications (defaults to WebX).
+    pub fn issuer(&self) -> &str {
+        self.issuer.as_deref().unwrap_or("WebX")
+    }
+
+    /// Whether users that are not enrolled are refused (defaults to false: only enrolled users must provide a one-time password).
+    pub fn required(&self) -> bool {
+        self.required.unwrap_or(false)
+    }
+}
+
+impl ResolutionSettings {
+    /// The minimum screen width in pixels (defaults to 320).
+    pub fn min_width(&self) -> u32 {
+        self.min_width.unwrap_or(320)
+    }
+
+    /// The minimum screen height in pixels (defaults to 240).
+    pub fn min_height(&self) -> u32 {
+        self.min_height.unwrap_or(240)
+    }
+
+    /// The maximum screen width in pixels (defaults to 8192).
+    pub fn max_width(&self) -> u32 {
+        self.max_width.unwrap_or(8192)
+    }
+
+    /// The maximum screen height in pixels (defaults to 8192).
+    pub fn max_height(&self) -> u32 {
+        self.max_height.unwrap_or(8192)
+    }
+
+    /// The maximum number of pixels of the screen (defaults to 7680x4320).
+    pub fn max_pixels(&self) -> u64 {
+        self.max_pixels.unwrap_or(7680 * 4320)
+    }
+
+    /// The multiple the width and height of a single-monitor screen are rounded to (defaults to 1, no rounding).
+    pub fn multiple(&self) -> u32 {
+        self.multiple.unwrap_or(1)
+    }
+
+    /// The modes (as widthxheight) single-monitor screens are restricted to. Any mode within the limits is allowed if empty.
+    pub fn modes(&self) -> Vec<ScreenResolution> {
+        self.modes
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|mode| mode.parse().ok())
+            .collect()
+    }
+}
+
+impl RateLimitSettings {
+    /// The number of failed attempts for a username before it is locked out (defaults to 5).
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts.unwrap_or(5)
+    }
+
+    /// The number of seconds after which failed attempts for a username are forgotten (defaults to 5 minutes).
+    pub fn window(&self) -> u64 {
+        self.window.unwrap_or(300)
+    }
+
+    /// The duration in seconds of the first lockout of a username (defaults to 1 minute).
+    /// Each successive lockout doubles in duration.
+    pub fn lockout(&self) -> u64 {
+        self.lockout.unwrap_or(60)
+    }
+
+    /// The maximum duration in seconds of a lockout (defaults to 1 hour).
+    pub fn max_lockout(&self) -> u64 {
+        self.max_lockout.unwrap_or(3600)
+    }
+
+    /// The number of login attempts, for all usernames, allowed within the global window (defaults to 30).
+    pub fn global_max_attempts(&self) -> u32 {
+        self.global_max_attempts.unwrap_or(30)
+    }
+
+    /// The duration in seconds of the global window (defaults to 1 minute).
+    pub fn global_window(&self) -> u64 {
+        self.global_window.unwrap_or(60)
+    }
+}
+
+impl ReconnectionSettings {
+    /// The number of seconds a reconnection token remains valid (defaults to 1 hour).
+    pub fn ttl(&self) -> u64 {
+        self.ttl.unwrap_or(3600)
+    }
+}
+
+impl TicketSettings {
+    pub fn keys(&self) -> &[TicketKeySettings] {
+        &self.keys
+    }
+
+    /// The maximum number of seconds a ticket may be valid for (defaults to 5 minutes).
+    pub fn max_validity(&self) -> u64 {
+        self.max_validity.unwrap_or(300)
+    }
+
+    /// The path to the root-only file persisting the used nonces across restarts.
+    pub fn nonce_store_path(&self) -> &str {
+        self.nonce_store_path.as_deref().unwrap_or("/var/lib/webx/webx-session-manager/ticket-nonces.json")
+    }
+}
+
+impl TicketKeySettings {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
 }
 
 impl LoggingSettings {
@@ -74,6 +449,10 @@ impl LoggingSettings {
     pub fn format(&self) -> &Option<String> {
         &self.format
     }
+
+    pub fn audit(&self) -> &Option<FileLoggingSettings> {
+        &self.audit
+    }
 }
 
 impl FileLoggingSettings {
@@ -110,15 +489,297 @@ impl XorgSettings {
         &self.config_path
     }
 
+    /// The display server launched for sessions when none is requested at login (defaults to xorgxrdp).
+    pub fn display_server(&self) -> DisplayServerKind {
+        self.display_server.unwrap_or(DisplayServerKind::Xorgxrdp)
+    }
+
+    /// The display servers that may be requested at login (defaults to the default display server only).
+    pub fn display_servers(&self) -> Vec<DisplayServerKind> {
+        let mut display_servers = self.display_servers.clone().unwrap_or_default();
+        if !display_servers.contains(&self.display_server()) {
+            display_servers.push(self.display_server());
+        }
+        display_servers
+    }
+
+    /// The path to the Xorg configuration using the dummy video driver.
+    pub fn xdummy_config_path(&self) -> &str {
+        self.xdummy_config_path.as_deref().unwrap_or("/etc/webx/webx-session-manager/xdummy.conf")
+    }
+
+    /// The directory containing the `.desktop` entries of the desktop environments (defaults to /usr/share/xsessions).
+    pub fn xsessions_path(&self) -> &str {
+        self.xsessions_path.as_deref().unwrap_or("/usr/share/xsessions")
+    }
+
+    /// The ids of the desktops offered to the users. All the installed desktops are offered if not specified.
+    pub fn desktops(&self) -> Option<&[String]> {
+        self.desktops.as_deref()
+    }
+
+    /// The script launching the application (and minimal window manager) of kiosk profiles.
+    pub fn kiosk_launcher(&self) -> &str {
+        self.kiosk_launcher.as_deref().unwrap_or("/etc/webx/webx-session-manager/startkiosk.sh")
+    }
+
+    /// Whether an existing session is resized to the resolution requested when the user logs in again. Disabled by default.
+    pub fn resize_on_login(&self) -> bool {
+        self.resize_on_login.unwrap_or(false)
+    }
+
+    /// The maximum number of monitors of a multi-monitor screen layout. 4 by default.
+    pub fn max_monitors(&self) -> usize {
+        self.max_monitors.unwrap_or(4)
+    }
+
+    /// The DPI of the sessions for which none is requested at login. 96 by default.
+    pub fn dpi(&self) -> u32 {
+        self.dpi.unwrap_or(96)
+    }
+
+    /// The color depth of the sessions for which none is requested at login. 24 by default.
+    pub fn depth(&self) -> u32 {
+        self.depth.unwrap_or(24)
+    }
+
+    /// The XKB rules listing the keyboard models, layouts and variants that can be requested at login.
+    pub fn xkb_rules_path(&self) -> &str {
+        self.xkb_rules_path.as_deref().unwrap_or("/usr/share/X11/xkb/rules/base.lst")
+    }
+
+    /// The directory containing the timezones that can be requested at login.
+    pub fn zoneinfo_path(&self) -> &str {
+        self.zoneinfo_path.as_deref().unwrap_or("/usr/share/zoneinfo")
+    }
+
+    /// The names of the environment variables that clients may provide at login. A name ending with `*` matches
+    /// all the variables starting with it. No variable is accepted if not specified.
+    pub fn client_environment(&self) -> &[String] {
+        self.client_environment.as_deref().unwrap_or_default()
+    }
+
+    /// The directory containing the unix sockets of the displays (defaults to /tmp/.X11-unix).
+    pub fn x11_socket_path(&self) -> &str {
+        self.x11_socket_path.as_deref().unwrap_or("/tmp/.X11-unix")
+    }
+
+    /// The limits applied to the requested screen resolutions. The default limits apply if not specified.
+    pub fn resolution(&self) -> &Option<ResolutionSettings> {
+        &self.resolution
+    }
+
     pub fn log_path(&self) -> &str {
         &self.log_path
     }
 }
 
+impl AccessSettings {
+    /// The users permitted to start a session. When both the allowed users and groups are empty, all users are permitted.
+    pub fn allowed_users(&self) -> &[String] {
+        self.allowed_users.as_deref().unwrap_or_default()
+    }
+
+    /// The users that are never permitted to start a session.
+    pub fn denied_users(&self) -> &[String] {
+        self.denied_users.as_deref().unwrap_or_default()
+    }
+
+    /// The groups whose members are permitted to start a session.
+    pub fn allowed_groups(&self) -> &[String] {
+        self.allowed_groups.as_deref().unwrap_or_default()
+    }
+
+    /// The groups whose members are never permitted to start a session.
+    pub fn denied_groups(&self) -> &[String] {
+        self.denied_groups.as_deref().unwrap_or_default()
+    }
+
+    /// Whether the root user (uid 0) is forbidden from starting a session (defaults to false).
+    pub fn deny_root(&self) -> bool {
+        self.deny_root.unwrap_or(false)
+    }
+
+    /// The minimum uid of users permitted to start a session, used to exclude system accounts.
+    pub fn minimum_uid(&self) -> Option<u32> {
+        self.minimum_uid
+    }
+
+    /// The time windows restricting when the members of groups may log in.
+    pub fn login_windows(&self) -> &[LoginWindowSettings] {
+        self.login_windows.as_deref().unwrap_or_default()
+    }
+
+    /// Whether running sessions are terminated when their user is no longer permitted by the login windows (defaults to false).
+    pub fn terminate_outside_windows(&self) -> bool {
+        self.terminate_outside_windows.unwrap_or(false)
+    }
+
+    /// The number of minutes before termination that a session user is warned (defaults to 15).
+    pub fn termination_warning(&self) -> u64 {
+        self.termination_warning.unwrap_or(15)
+    }
+}
+
+impl ProfileSettings {
+    /// The name of the profile, provided at login.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The display server launched for the sessions of the profile (defaults to the Xorg display server).
+    pub fn display_server(&self) -> Option<DisplayServerKind> {
+        self.display_server
+    }
+
+    /// The Xorg configuration used by the xorgxrdp and xdummy display servers (defaults to the Xorg configuration).
+    pub fn config_path(&self) -> Option<&str> {
+        self.config_path.as_deref()
+    }
+
+    /// The command of the single application of a kiosk profile.
+    pub fn application(&self) -> Option<&str> {
+        self.application.as_deref()
+    }
+
+    /// The command of the window manager: the minimal window manager running under the application of a kiosk profile,
+    /// otherwise the command replacing the window manager script.
+    pub fn window_manager(&self) -> Option<&str> {
+        self.window_manager.as_deref()
+    }
+
+    /// What happens when the application of a kiosk profile exits (defaults to logout).
+    pub fn on_exit(&self) -> ProfileExitPolicy {
+        self.on_exit.unwrap_or(ProfileExitPolicy::Logout)
+    }
+
+    /// The groups whose members may use the profile. All users may use the profile if empty.
+    pub fn groups(&self) -> &[String] {
+        self.groups.as_deref().unwrap_or_default()
+    }
+
+    /// The environment variables added to the window manager (or application) of the sessions.
+    pub fn env(&self) -> HashMap<String, String> {
+        self.env.clone().unwrap_or_default()
+    }
+
+    /// The resource limits applied to the processes of the sessions.
+    pub fn limits(&self) -> ResourceLimitSettings {
+        self.limits.clone().unwrap_or_default()
+    }
+
+    /// The number of milliseconds to wait for the display server to start before launching the window manager (defaults to 1000).
+    pub fn startup_wait(&self) -> u64 {
+        self.startup_wait.unwrap_or(1000)
+    }
+
+    /// The maximum duration of the sessions in minutes, after which they are terminated.
+    pub fn max_duration(&self) -> Option<u64> {
+        self.max_duration
+    }
+
+    /// The number of minutes before the maximum duration at which the users are warned (defaults to 5).
+    pub fn max_duration_warning(&self) -> u64 {
+        self.max_duration_warning.unwrap_or(5)
+    }
+}
+
+impl ResourceLimitSettings {
+    /// The maximum address space of each process in megabytes.
+    pub fn memory(&self) -> Option<u64> {
+        self.memory
+    }
+
+    /// The maximum number of processes of the user.
+    pub fn processes(&self) -> Option<u64> {
+        self.processes
+    }
+
+    /// The maximum number of open files of each process.
+    pub fn open_files(&self) -> Option<u64> {
+        self.open_files
+    }
+
+    /// The maximum CPU time of each process in seconds.
+    pub fn cpu_time(&self) -> Option<u64> {
+        self.cpu_time
+    }
+}
+
+impl RevalidationSettings {
+    /// The number of seconds between two revalidations of the session users (defaults to 300).
+    pub fn interval(&self) -> u64 {
+        self.interval.unwrap_or(300)
+    }
+
+    /// The action taken on sessions whose user no longer qualifies (defaults to terminate).
+    pub fn action(&self) -> RevalidationAction {
+        self.action.unwrap_or(RevalidationAction::Terminate)
+    }
+}
+
+impl DrainSettings {
+    /// The path to the root-only file persisting the drain mode across restarts.
+    pub fn state_path(&self) -> &str {
+        self.state_path.as_deref().unwrap_or("/var/lib/webx/webx-session-manager/drain.json")
+    }
+
+    /// The number of minutes before the drain deadline at which the users are warned (defaults to 15).
+    pub fn warning(&self) -> u64 {
+        self.warning.unwrap_or(15)
+    }
+}
+
+impl LoginWindowSettings {
+    /// The name of the window, used in log messages.
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or("unnamed")
+    }
+
+    /// The groups the window applies to.
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
+    /// The weekdays (mon, tue...) on which the window opens. The window opens every day if not specified.
+    pub fn days(&self) -> &[String] {
+        self.days.as_deref().unwrap_or_default()
+    }
+
+    /// The time (HH:MM) at which the window opens.
+    pub fn start(&self) -> &str {
+        &self.start
+    }
+
+    /// The time (HH:MM) at which the window closes.
+    pub fn end(&self) -> &str {
+        &self.end
+    }
+
+    /// The policy of the window (defaults to restrict).
+    pub fn policy(&self) -> LoginWindowPolicy {
+        self.policy.unwrap_or(LoginWindowPolicy::Restrict)
+    }
+
+    /// The users the window does not apply to.
+    pub fn exempt_users(&self) -> &[String] {
+        self.exempt_users.as_deref().unwrap_or_default()
+    }
+
+    /// The groups whose members the window does not apply to.
+    pub fn exempt_groups(&self) -> &[String] {
+        self.exempt_groups.as_deref().unwrap_or_default()
+    }
+}
+
 impl TransportSettings {
     pub fn ipc(&self) -> &str {
         &self.ipc
     }
+
+    pub fn admin_ipc(&self) -> &Option<String> {
+        &self.admin_ipc
+    }
 }
 
 static DEFAULT_CONFIG_PATHS: [&str; 2] = ["/etc/webx/webx-session-manager-config.yml", "./config.yml"];
@@ -174,6 +835,43 @@ impl Settings {
         &self.xorg
     }
 
+    /// Returns the access control settings.
+    ///
+    /// # Returns
+    /// A reference to the optional `AccessSettings`.
+    pub fn access(&self) -> &Option<AccessSettings> {
+        &self.access
+    }
+
+    /// Returns the session profiles that can be chosen at login.
+    ///
+    /// # Returns
+    /// A slice of `ProfileSettings`.
+    pub fn profiles(&self) -> &[ProfileSettings] {
+        self.profiles.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the name of the profile applied when none is chosen at login, if any.
+    pub fn default_profile(&self) -> Option<&str> {
+        self.default_profile.as_deref()
+    }
+
+    /// Returns the session revalidation settings.
+    ///
+    /// # Returns
+    /// A reference to the optional `RevalidationSettings`.
+    pub fn revalidation(&self) -> &Option<RevalidationSettings> {
+        &self.revalidation
+    }
+
+    /// Returns the drain mode settings, or the defaults if they are not configured.
+    ///
+    /// # Returns
+    /// The `DrainSettings`.
+    pub fn drain(&self) -> DrainSettings {
+        self.drain.clone().unwrap_or_default()
+    }
+
     /// Determines the configuration file path to use.
     ///
     /// # Arguments
@@ -216,16 +914,86 @@ impl Settings {
             }
         }
 
+        if let Some(audit) = &self.logging.audit {
+            if audit.enabled.unwrap_or(false) && audit.path.is_empty() {
+                eprintln!("Please specify a path for the audit log file");
+                return false;
+            }
+        }
+
         if self.authentication.service.is_empty() {
             eprintln!("Please specify a PAM service to use (i.e. login)");
             return false;
         }
 
+        if let Some(tickets) = &self.authentication.tickets {
+            if tickets.keys.is_empty() || tickets.keys.iter().any(|key| key.id.is_empty() || key.secret.is_empty()) {
+                eprintln!("Please specify an id and a secret for each ticket key");
+                return false;
+            }
+        }
+
+        for (index, profile) in self.profiles().iter().enumerate() {
+            if profile.name.is_empty() || self.profiles().iter().skip(index + 1).any(|other| other.name == profile.name) {
+                eprintln!("Please specify a unique name for each session profile");
+                return false;
+            }
+            if profile.application.as_ref().is_some_and(|application| application.trim().is_empty()) {
+                eprintln!("Please specify the application command of the kiosk profile {}", profile.name);
+                return false;
+            }
+        }
+
+        if let Some(default_profile) = &self.default_profile {
+            if !self.profiles().iter().any(|profile| &profile.name == default_profile) {
+                eprintln!("The default profile {} is not defined in the session profiles", default_profile);
+                return false;
+            }
+        }
+
+        if let Some(revalidation) = &self.revalidation {
+            if revalidation.interval() == 0 {
+                eprintln!("Please specify a revalidation interval greater than 0 seconds");
+                return false;
+            }
+        }
+
+        if let Some(totp) = &self.authentication.totp {
+            if totp.issuer().contains(':') || totp.store_path().is_empty() {
+                eprintln!("Please specify a path for the TOTP store and an issuer that does not contain ':'");
+                return false;
+            }
+        }
+
         if self.transport.ipc.is_empty() {
             eprintln!("Please specify a path to the ipc socket (i.e. /tmp/webx-session-manager.ipc)");
             return false;
         }
 
+        if let Some(admin_ipc) = &self.transport.admin_ipc {
+            if admin_ipc.is_empty() || admin_ipc == &self.transport.ipc {
+                eprintln!("Please specify a distinct path to the admin ipc socket (i.e. /tmp/webx-session-manager-admin.ipc)");
+                return false;
+            }
+        }
+
+        if let Some(access) = &self.access {
+            for login_window in access.login_windows() {
+                if login_window.groups.is_empty() {
+                    eprintln!("Please specify the groups of the login window {}", login_window.name());
+                    return false;
+                }
+                if NaiveTime::parse_from_str(&login_window.start, "%H:%M").is_err() || NaiveTime::parse_from_str(&login_window.end, "%H:%M").is_err() {
+                    eprintln!("Please specify the start and end of the login window {} as HH:MM", login_window.name());
+                    return false;
+                }
+                if login_window.days().iter().any(|day| day.parse::<Weekday>().is_err()) {
+                    eprintln!("Please specify the days of the login window {} as mon, tue, wed, thu, fri, sat or sun", login_window.name());
+                    return false;
+                }
+            }
+        }
+
         if self.xorg.sessions_path.is_empty() {
             eprintln!("Please specify a path for where to store the session files (i.e. /run/webx/sessions");
             return false;
@@ -241,6 +1009,26 @@ impl Settings {
             return false;
         }
 
+        if !DisplayProperties::new(self.xorg.dpi(), self.xorg.depth()).is_supported() {
+            eprintln!("Please specify a DPI between {} and {} and a color depth among {:?}", DisplayProperties::MIN_DPI, DisplayProperties::MAX_DPI, DisplayProperties::DEPTHS);
+            return false;
+        }
+
+        if let Some(resolution) = &self.xorg.resolution {
+            if resolution.min_width() == 0 || resolution.min_height() == 0 || resolution.min_width() > resolution.max_width() || resolution.min_height() > resolution.max_height() {
+                eprintln!("Please specify minimum resolution dimensions greater than 0 and not exceeding the maximum dimensions");
+                return false;
+            }
+            if resolution.multiple() == 0 {
+                eprintln!("Please specify a resolution multiple greater than 0");
+                return false;
+            }
+            if let Some(mode) = resolution.modes.iter().flatten().find(|mode| mode.parse::<ScreenResolution>().is_err()) {
+                eprintln!("Please specify the resolution mode {} as widthxheight", mode);
+                return false;
+            }
+        }
+
         if self.xorg.log_path.is_empty() {
             eprintln!("Please specify a path to store the session logs i.e. /var/log/webx/webx-session-manager/sessions");
             return false;
@@ -1,5 +1,9 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::common::{KeyboardLayout, Monitor, NotificationUrgency, SessionFilter};
+
 /// The `Request` enum represents the possible requests that can be sent to the WebX Session Manager server.
 /// Each variant corresponds to a specific type of request.
 #[derive(Serialize, Deserialize)]
@@ -12,12 +16,119 @@ pub enum Request {
     /// * `password` - The password of the user.
     /// * `width` - The screen width for the session.
     /// * `height` - The screen height for the session.
+    /// * `otp` - The one-time password, required for users enrolled in the TOTP second factor.
+    /// * `display_server` - The display server backend (xorgxrdp, xvfb, xdummy or xvnc), or `None` for the default.
+    /// * `desktop` - The id of the desktop environment (see the desktops request), or `None` for the default window manager.
+    /// * `profile` - The name of the session profile (such as a kiosk profile), or `None` for none.
+    /// * `monitors` - The monitor layout of a multi-monitor session, whose bounding box replaces the width and height.
+    /// * `dpi` - The DPI of the display, or `None` for the default.
+    /// * `depth` - The color depth of the display, or `None` for the default.
+    /// * `keyboard` - The XKB keyboard model, layout and variant, or `None` for the default.
+    /// * `locale` - The locale of the desktop (for example `fr_CH.UTF-8`), or `None` for the default of the host.
+    /// * `timezone` - The timezone of the desktop (for example `Europe/Zurich`), or `None` for the default of the host.
+    /// * `environment` - Extra environment variables for the session, only those permitted by the settings being applied.
+    /// * `labels` - Metadata of the session (such as the portal instance, the client address or the browser), returned with the session details.
     #[serde(rename = "login")]
-    Login { username: String, password: String, width: u32, height: u32 },
+    Login {
+        username: String,
+        password: String,
+        width: u32,
+        height: u32,
+        #[serde(default)]
+        otp: Option<String>,
+        #[serde(default)]
+        display_server: Option<String>,
+        #[serde(default)]
+        desktop: Option<String>,
+        #[serde(default)]
+        profile: Option<String>,
+        #[serde(default)]
+        monitors: Option<Vec<Monitor>>,
+        #[serde(default)]
+        dpi: Option<u32>,
+        #[serde(default)]
+        depth: Option<u32>,
+        #[serde(default)]
+        keyboard: Option<KeyboardLayout>,
+        #[serde(default)]
+        locale: Option<String>,
+        #[serde(default)]
+        timezone: Option<String>,
+        #[serde(default)]
+        environment: Option<HashMap<String, String>>,
+        #[serde(default)]
+        labels: Option<HashMap<String, String>>,
+    },
+
+    /// A request to create a new session for a user that has been pre-authenticated
+    /// and holds a signed, short-lived login ticket.
+    ///
+    /// # Fields
+    /// * `ticket` - The signed login ticket.
+    /// * `width` - The screen width for the session.
+    /// * `height` - The screen height for the session.
+    /// * `display_server` - The display server backend, or `None` for the default.
+    /// * `desktop` - The id of the desktop environment, or `None` for the default window manager.
+    /// * `profile` - The name of the session profile, or `None` for none.
+    /// * `monitors` - The monitor layout of a multi-monitor session, whose bounding box replaces the width and height.
+    /// * `dpi` - The DPI of the display, or `None` for the default.
+    /// * `depth` - The color depth of the display, or `None` for the default.
+    /// * `keyboard` - The XKB keyboard model, layout and variant, or `None` for the default.
+    /// * `locale` - The locale of the desktop (for example `fr_CH.UTF-8`), or `None` for the default of the host.
+    /// * `timezone` - The timezone of the desktop (for example `Europe/Zurich`), or `None` for the default of the host.
+    /// * `environment` - Extra environment variables for the session, only those permitted by the settings being applied.
+    /// * `labels` - Metadata of the session (such as the portal instance, the client address or the browser), returned with the session details.
+    #[serde(rename = "login_ticket")]
+    LoginTicket {
+        ticket: String,
+        width: u32,
+        height: u32,
+        #[serde(default)]
+        display_server: Option<String>,
+        #[serde(default)]
+        desktop: Option<String>,
+        #[serde(default)]
+        profile: Option<String>,
+        #[serde(default)]
+        monitors: Option<Vec<Monitor>>,
+        #[serde(default)]
+        dpi: Option<u32>,
+        #[serde(default)]
+        depth: Option<u32>,
+        #[serde(default)]
+        keyboard: Option<KeyboardLayout>,
+        #[serde(default)]
+        locale: Option<String>,
+        #[serde(default)]
+        timezone: Option<String>,
+        #[serde(default)]
+        environment: Option<HashMap<String, String>>,
+        #[serde(default)]
+        labels: Option<HashMap<String, String>>,
+    },
 
-    /// A request to list all active sessions.
+    /// A request to retrieve an existing session using a reconnection token rather than the user's credentials.
+    ///
+    /// # Fields
+    /// * `token` - The reconnection token returned by a previous login.
+    #[serde(rename = "reconnect")]
+    Reconnect { token: String },
+
+    /// A request to list the desktop environments that can be chosen at login.
+    #[serde(rename = "desktops")]
+    Desktops,
+
+    /// A request to list the active sessions, optionally restricted by a filter (such as the user, the state or the labels
+    /// of the sessions) and sorted.
     #[serde(rename = "who")]
-    Who,
+    Who(#[serde(default)] Option<SessionFilter>),
+
+    /// A request to retrieve a single session.
+    ///
+    /// # Fields
+    /// * `id` - The ID of the session.
+    #[serde(rename = "get")]
+    Get { id: String },
 
     /// A request to log out a user and terminate the session.
     ///
@@ -25,6 +136,157 @@ pub enum Request {
     /// * `id` - The session ID to terminate.
     #[serde(rename = "logout")]
     Logout { id: String },
+
+    /// An admin request to log out all the sessions matching a filter (such as the user or the labels of the sessions).
+    /// A filter without criteria is only accepted with `all`, so that all the sessions are not logged out by mistake.
+    ///
+    /// # Fields
+    /// * `filter` - The criteria of the sessions to log out.
+    /// * `all` - Whether all the sessions are logged out when the filter has no criteria.
+    #[serde(rename = "logout_sessions")]
+    LogoutSessions {
+        #[serde(default)]
+        filter: SessionFilter,
+        #[serde(default)]
+        all: bool,
+    },
+
+    /// A request to change the screen resolution of a running session.
+    ///
+    /// # Fields
+    /// * `id` - The session ID to resize.
+    /// * `width` - The new screen width.
+    /// * `height` - The new screen height.
+    /// * `monitors` - The new monitor layout, whose bounding box replaces the width and height.
+    #[serde(rename = "resize")]
+    Resize {
+        id: String,
+        width: u32,
+        height: u32,
+        #[serde(default)]
+        monitors: Option<Vec<Monitor>>,
+    },
+
+    /// A request to grant another user access to a session, with a cookie of their own.
+    ///
+    /// # Fields
+    /// * `id` - The ID of the session.
+    /// * `grantee` - The username of the user granted access.
+    /// * `view_only` - Whether the grantee may only view the session.
+    /// * `cookie` - The cookie of the session, proving that the request is made for its owner (not required on the admin socket).
+    #[serde(rename = "grant")]
+    Grant {
+        id: String,
+        grantee: String,
+        #[serde(default)]
+        view_only: bool,
+        #[serde(default)]
+        cookie: Option<String>,
+    },
+
+    /// A request to revoke the access to a session granted to a user.
+    ///
+    /// # Fields
+    /// * `id` - The ID of the session.
+    /// * `grantee` - The username of the user granted access.
+    /// * `cookie` - The cookie of the session, proving that the request is made for its owner (not required on the admin socket).
+    #[serde(rename = "revoke_grant")]
+    RevokeGrant {
+        id: String,
+        grantee: String,
+        #[serde(default)]
+        cookie: Option<String>,
+    },
+
+    /// An admin request to list the usernames that are locked out after too many failed login attempts.
+    #[serde(rename = "lockouts")]
+    Lockouts,
+
+    /// An admin request to clear the lockout of a username, or of all usernames.
+    ///
+    /// # Fields
+    /// * `username` - The username to clear, or `None` to clear all usernames.
+    #[serde(rename = "clear_lockouts")]
+    ClearLockouts { username: Option<String> },
+
+    /// An admin request to enroll a user in the TOTP second factor, replacing any existing secret.
+    ///
+    /// # Fields
+    /// * `username` - The username of the user to enroll.
+    #[serde(rename = "totp_enroll")]
+    TotpEnroll { username: String },
+
+    /// An admin request to remove the TOTP enrollment of a user.
+    ///
+    /// # Fields
+    /// * `username` - The username of the user.
+    #[serde(rename = "totp_reset")]
+    TotpReset { username: String },
+
+    /// An admin request to replace the cookie given to the clients of a session, revoking the previous cookie
+    /// without terminating the session.
+    ///
+    /// # Fields
+    /// * `id` - The ID of the session.
+    #[serde(rename = "rotate_cookie")]
+    RotateCookie { id: String },
+
+    /// An admin request to enable the drain mode: new logins are rejected while the existing sessions keep running,
+    /// until the deadline (if any) at which they are terminated. The drain mode survives restarts.
+    ///
+    /// # Fields
+    /// * `allow_existing` - Whether the users that already have a session can still log in to it.
+    /// * `deadline` - The unix timestamp at which the remaining sessions are terminated, if any.
+    #[serde(rename = "drain")]
+    Drain {
+        #[serde(default)]
+        allow_existing: bool,
+        #[serde(default)]
+        deadline: Option<i64>,
+    },
+
+    /// An admin request to disable the drain mode.
+    #[serde(rename = "undrain")]
+    Undrain,
+
+    /// An admin request to retrieve the drain mode.
+    #[serde(rename = "drain_status")]
+    DrainStatus,
+
+    /// An admin request to show a desktop notification in sessions: those with the given IDs, those matching a filter,
+    /// or all the sessions. A filter without criteria is only accepted with `all`.
+    ///
+    /// # Fields
+    /// * `ids` - The IDs of the sessions, if the notification is only shown in these sessions.
+    /// * `filter` - The criteria of the sessions.
+    /// * `all` - Whether the notification is shown in all the sessions when no IDs or criteria are given.
+    /// * `summary` - The summary (title) of the notification.
+    /// * `body` - The body of the notification, if any.
+    /// * `urgency` - The urgency of the notification (low, normal or critical).
+    #[serde(rename = "notify")]
+    Notify {
+        #[serde(default)]
+        ids: Vec<String>,
+        #[serde(default)]
+        filter: SessionFilter,
+        #[serde(default)]
+        all: bool,
+        summary: String,
+        #[serde(default)]
+        body: Option<String>,
+        #[serde(default)]
+        urgency: NotificationUrgency,
+    },
+}
+
+impl Request {
+    /// Determines whether the request is an admin request, only accepted on the admin socket.
+    ///
+    /// # Returns
+    /// `true` if the request requires admin privileges, otherwise `false`.
+    pub fn is_admin(&self) -> bool {
+        matches!(self, Request::Lockouts | Request::ClearLockouts { .. } | Request::TotpEnroll { .. } | Request::TotpReset { .. } | Request::RotateCookie { .. } | Request::LogoutSessions { .. } | Request::Drain { .. } | Request::Undrain | Request::DrainStatus | Request::Notify { .. })
+    }
 }
 
 
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::dto::SessionDto;
+use crate::dto::{DesktopDto, DrainDto, GrantDto, LockoutDto, LogoutDto, NotificationDto, SessionDto};
 
 /// The `Response` enum represents the possible responses sent by the WebX Session Manager server.
 /// Each variant corresponds to a specific type of response.
@@ -11,6 +11,14 @@ pub enum Response {
     #[serde(rename = "login")]
     Login(SessionDto),
 
+    /// A response to a reconnection request, containing the session details and a new reconnection token.
+    #[serde(rename = "reconnect")]
+    Reconnect(SessionDto),
+
+    /// A response listing the desktop environments that can be chosen at login.
+    #[serde(rename = "desktops")]
+    Desktops(Vec<DesktopDto>),
+
     /// A response listing all active sessions.
     #[serde(rename = "who")]
     Who(Vec<SessionDto>),
@@ -22,4 +30,72 @@ pub enum Response {
     /// A response indicating a successful logout.
     #[serde(rename = "logout")]
     Logout,
+
+    /// A response to a bulk logout request, containing the result of the logout of each matching session.
+    #[serde(rename = "logout_sessions")]
+    LogoutSessions(Vec<LogoutDto>),
+
+    /// A response to a get request, containing the session details.
+    #[serde(rename = "get")]
+    Get(SessionDto),
+
+    /// A response to a resize request, containing the session details with the new resolution.
+    #[serde(rename = "resize")]
+    Resize(SessionDto),
+
+    /// A response to a grant request, containing the access granted with its cookie.
+    #[serde(rename = "grant")]
+    Grant(GrantDto),
+
+    /// A response indicating whether the access to a session granted to a user has been revoked.
+    #[serde(rename = "revoke_grant")]
+    RevokeGrant { revoked: bool },
+
+    /// A response indicating that the login was rejected because too many login attempts have been made.
+    #[serde(rename = "locked_out")]
+    LockedOut { message: String },
+
+    /// A response indicating that the login was rejected because the host is being drained.
+    #[serde(rename = "draining")]
+    Draining { message: String },
+
+    /// A response indicating that the requested screen resolution or monitor layout was rejected, before any session process is spawned.
+    #[serde(rename = "invalid_resolution")]
+    InvalidResolution { message: String },
+
+    /// A response listing the usernames that are currently locked out.
+    #[serde(rename = "lockouts")]
+    Lockouts(Vec<LockoutDto>),
+
+    /// A response indicating the number of lockouts that have been cleared.
+    #[serde(rename = "clear_lockouts")]
+    ClearLockouts { cleared: usize },
+
+    /// A response to a TOTP enrollment, containing the otpauth URI to register in an authenticator application.
+    #[serde(rename = "totp_enroll")]
+    TotpEnroll { uri: String },
+
+    /// A response indicating whether the TOTP enrollment of a user has been removed.
+    #[serde(rename = "totp_reset")]
+    TotpReset { reset: bool },
+
+    /// A response to a cookie rotation request, containing the session details with the new cookie.
+    #[serde(rename = "rotate_cookie")]
+    RotateCookie(SessionDto),
+
+    /// A response to a drain request, containing the drain mode.
+    #[serde(rename = "drain")]
+    Drain(DrainDto),
+
+    /// A response to an undrain request, containing the drain mode.
+    #[serde(rename = "undrain")]
+    Undrain(DrainDto),
+
+    /// A response to a drain status request, containing the drain mode.
+    #[serde(rename = "drain_status")]
+    DrainStatus(DrainDto),
+
+    /// A response to a notify request, containing the result of the notification of each session.
+    #[serde(rename = "notify")]
+    Notify(Vec<NotificationDto>),
 }
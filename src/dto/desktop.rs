@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::Desktop;
+
+/// The `DesktopDto` struct represents a data transfer object for a desktop environment that can be chosen at login.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DesktopDto {
+    id: String,
+    name: String,
+    comment: String,
+}
+
+impl DesktopDto {
+    /// Returns the id of the desktop, to provide in the login request.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the name of the desktop.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the description of the desktop.
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+}
+
+impl From<&Desktop> for DesktopDto {
+    /// Converts a `Desktop` into a `DesktopDto`.
+    ///
+    /// # Arguments
+    /// * `desktop` - The `Desktop` to convert.
+    ///
+    /// # Returns
+    /// A `DesktopDto` instance.
+    fn from(desktop: &Desktop) -> Self {
+        Self {
+            id: desktop.id().into(),
+            name: desktop.name().into(),
+            comment: desktop.comment().into(),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::services::DrainState;
+
+/// The `DrainDto` struct represents a data transfer object for the drain mode of the session manager.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DrainDto {
+    draining: bool,
+    allow_existing: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deadline: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    started: Option<i64>,
+    sessions: usize,
+}
+
+impl DrainDto {
+    /// Creates a new `DrainDto` instance.
+    ///
+    /// # Arguments
+    /// * `state` - The drain mode, or `None` if the session manager is not draining.
+    /// * `sessions` - The number of remaining sessions.
+    ///
+    /// # Returns
+    /// A new `DrainDto` instance.
+    pub fn new(state: Option<&DrainState>, sessions: usize) -> Self {
+        Self {
+            draining: state.is_some(),
+            allow_existing: state.is_some_and(|state| state.allow_existing()),
+            deadline: state.and_then(|state| state.deadline()),
+            started: state.map(|state| state.started()),
+            sessions,
+        }
+    }
+
+    /// Returns whether the session manager is draining.
+    pub fn is_draining(&self) -> bool {
+        self.draining
+    }
+
+    /// Returns whether the users that already have a session can still log in to it.
+    pub fn allow_existing(&self) -> bool {
+        self.allow_existing
+    }
+
+    /// Returns the unix timestamp at which the remaining sessions are terminated, if any.
+    pub fn deadline(&self) -> Option<i64> {
+        self.deadline
+    }
+
+    /// Returns the unix timestamp at which the drain mode was enabled, if the session manager is draining.
+    pub fn started(&self) -> Option<i64> {
+        self.started
+    }
+
+    /// Returns the number of remaining sessions.
+    pub fn sessions(&self) -> usize {
+        self.sessions
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::Grant;
+
+/// The `GrantDto` struct represents a data transfer object for the access to a session granted to another user.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GrantDto {
+    grantee: String,
+    view_only: bool,
+    xauthority_file_path: String,
+    created: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cookie: Option<String>,
+}
+
+impl GrantDto {
+    /// Adds the cookie of the Xauthority file of the grantee to the `GrantDto`.
+    /// The cookie is only returned when access is granted and to admin requests.
+    ///
+    /// # Arguments
+    /// * `cookie` - The hexadecimal MIT-MAGIC-COOKIE-1 cookie.
+    ///
+    /// # Returns
+    /// The `GrantDto` including the cookie.
+    pub fn with_cookie(mut self, cookie: String) -> Self {
+        self.cookie = Some(cookie);
+        self
+    }
+
+    /// Returns the username of the user granted access.
+    pub fn grantee(&self) -> &str {
+        &self.grantee
+    }
+
+    /// Returns whether the grantee may only view the session.
+    pub fn is_view_only(&self) -> bool {
+        self.view_only
+    }
+
+    /// Returns the path to the Xauthority file of the grantee.
+    pub fn xauthority_file_path(&self) -> &str {
+        &self.xauthority_file_path
+    }
+
+    /// Returns the unix timestamp at which access was granted.
+    pub fn created(&self) -> i64 {
+        self.created
+    }
+
+    /// Returns the cookie of the Xauthority file of the grantee, if it was returned.
+    pub fn cookie(&self) -> Option<&str> {
+        self.cookie.as_deref()
+    }
+}
+
+impl fmt::Display for GrantDto {
+    /// Formats the `GrantDto` as a string in the format "grantee (view only)".
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}{}", self.grantee, if self.view_only { " (view only)" } else { "" })
+    }
+}
+
+impl From<&Grant> for GrantDto {
+    /// Converts a `Grant` into a `GrantDto`.
+    ///
+    /// # Arguments
+    /// * `grant` - The `Grant` to convert.
+    ///
+    /// # Returns
+    /// A `GrantDto` instance.
+    fn from(grant: &Grant) -> Self {
+        Self {
+            grantee: grant.grantee().into(),
+            view_only: grant.is_view_only(),
+            xauthority_file_path: grant.xauthority_file_path().into(),
+            created: grant.created(),
+            cookie: None,
+        }
+    }
+}
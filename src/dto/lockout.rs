@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use crate::authentication::Lockout;
+
+/// The `LockoutDto` struct represents a data transfer object for a username that is locked out
+/// after too many failed login attempts.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LockoutDto {
+    username: String,
+    lockouts: u32,
+    locked_until: i64,
+}
+
+impl LockoutDto {
+    /// Returns the username that is locked out.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Returns the number of successive lockouts of the username.
+    pub fn lockouts(&self) -> u32 {
+        self.lockouts
+    }
+
+    /// Returns the end of the lockout as a unix timestamp.
+    pub fn locked_until(&self) -> i64 {
+        self.locked_until
+    }
+}
+
+impl From<&Lockout> for LockoutDto {
+    /// Converts a `Lockout` into a `LockoutDto`.
+    ///
+    /// # Arguments
+    /// * `lockout` - The `Lockout` to convert.
+    ///
+    /// # Returns
+    /// A `LockoutDto` instance.
+    fn from(lockout: &Lockout) -> Self {
+        Self {
+            username: lockout.username().into(),
+            lockouts: lockout.lockouts(),
+            locked_until: lockout.locked_until(),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// The `LogoutDto` struct represents a data transfer object for the result of the logout of a session
+/// by a bulk logout request.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LogoutDto {
+    id: String,
+    username: String,
+    logged_out: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl LogoutDto {
+    /// Creates a new `LogoutDto` instance.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the session.
+    /// * `username` - The username of the session owner.
+    /// * `error` - The reason the session could not be logged out, or `None` if it has been logged out.
+    ///
+    /// # Returns
+    /// A new `LogoutDto` instance.
+    pub fn new(id: String, username: String, error: Option<String>) -> Self {
+        Self {
+            id,
+            username,
+            logged_out: error.is_none(),
+            error,
+        }
+    }
+
+    /// Returns the ID of the session.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the username of the session owner.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Returns whether the session has been logged out.
+    pub fn is_logged_out(&self) -> bool {
+        self.logged_out
+    }
+
+    /// Returns the reason the session could not be logged out, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
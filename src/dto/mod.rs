@@ -1,4 +1,15 @@
+pub use desktop::DesktopDto;
+pub use drain::DrainDto;
+pub use grant::GrantDto;
+pub use lockout::LockoutDto;
+pub use logout::LogoutDto;
+pub use notification::NotificationDto;
 pub use session::SessionDto;
-mod session;
-
 
+mod desktop;
+mod drain;
+mod grant;
+mod lockout;
+mod logout;
+mod notification;
+mod session;
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// The `NotificationDto` struct represents a data transfer object for the result of a desktop notification
+/// shown in a session by a notify request.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NotificationDto {
+    id: String,
+    username: String,
+    notified: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl NotificationDto {
+    /// Creates a new `NotificationDto` instance.
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the session.
+    /// * `username` - The username of the session owner.
+    /// * `error` - The reason the notification could not be shown, or `None` if it has been shown.
+    ///
+    /// # Returns
+    /// A new `NotificationDto` instance.
+    pub fn new(id: String, username: String, error: Option<String>) -> Self {
+        Self {
+            id,
+            username,
+            notified: error.is_none(),
+            error,
+        }
+    }
+
+    /// Returns the ID of the session.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the username of the session owner.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Returns whether the notification has been shown.
+    pub fn is_notified(&self) -> bool {
+        self.notified
+    }
+
+    /// Returns the reason the notification could not be shown, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
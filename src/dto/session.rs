@@ -1,8 +1,11 @@
+use std::collections::HashMap;
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
 
-use crate::common::Session;
+use crate::common::{Monitor, Session};
+
+use super::GrantDto;
 
 /// The `SessionDto` struct represents a data transfer object for a user session.
 /// It contains details about the session, such as the user, session ID, and process IDs.
@@ -16,7 +19,29 @@ pub struct SessionDto {
     window_manager_process_id: u32,
     xauthority_file_path: String,
     width: u32,
-    height: u32
+    height: u32,
+    #[serde(default)]
+    display_server: String,
+    #[serde(default)]
+    created: i64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    monitors: Vec<Monitor>,
+    #[serde(default)]
+    dpi: u32,
+    #[serde(default)]
+    depth: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    profile: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    labels: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cookie: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    grants: Vec<GrantDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reconnection_token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    revalidation_failure: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -33,6 +58,7 @@ impl SessionDto {
     /// * `xauthority_file_path` - The path to the Xauthority file.
     /// * `width` - The screen width.
     /// * `height` - The screen height.
+    /// * `display_server` - The display server backend.
     ///
     /// # Returns
     /// A new `SessionDto` instance.
@@ -46,7 +72,8 @@ impl SessionDto {
         window_manager_process_id: u32,
         xauthority_file_path: String,
         width: u32,
-        height: u32
+        height: u32,
+        display_server: String,
     ) -> Self {
         Self {
             id,
@@ -57,10 +84,133 @@ impl SessionDto {
             window_manager_process_id,
             xauthority_file_path,
             width,
-            height
+            height,
+            display_server,
+            created: 0,
+            monitors: vec![],
+            dpi: 96,
+            depth: 24,
+            profile: None,
+            labels: HashMap::new(),
+            cookie: None,
+            grants: vec![],
+            reconnection_token: None,
+            revalidation_failure: None,
         }
     }
 
+    /// Adds a reconnection token to the `SessionDto`.
+    /// The token is only returned to the client that logged in, never in the list of sessions.
+    ///
+    /// # Arguments
+    /// * `reconnection_token` - The opaque reconnection token bound to the session.
+    ///
+    /// # Returns
+    /// The `SessionDto` including the reconnection token.
+    pub fn with_reconnection_token(mut self, reconnection_token: String) -> Self {
+        self.reconnection_token = Some(reconnection_token);
+        self
+    }
+
+    /// Adds the cookie of the Xauthority file of the session to the `SessionDto`.
+    /// The cookie is only returned to the client that logged in and to admin requests.
+    ///
+    /// # Arguments
+    /// * `cookie` - The hexadecimal MIT-MAGIC-COOKIE-1 cookie.
+    ///
+    /// # Returns
+    /// The `SessionDto` including the cookie.
+    pub fn with_cookie(mut self, cookie: String) -> Self {
+        self.cookie = Some(cookie);
+        self
+    }
+
+    /// Adds the access to the session granted to other users to the `SessionDto`.
+    ///
+    /// # Arguments
+    /// * `grants` - The grants of the session.
+    ///
+    /// # Returns
+    /// The `SessionDto` including the grants.
+    pub fn with_grants(mut self, grants: Vec<GrantDto>) -> Self {
+        self.grants = grants;
+        self
+    }
+
+    /// Adds the time at which the session was created to the `SessionDto`.
+    ///
+    /// # Arguments
+    /// * `created` - The unix timestamp at which the session was created.
+    ///
+    /// # Returns
+    /// The `SessionDto` including the creation time.
+    pub fn with_created(mut self, created: i64) -> Self {
+        self.created = created;
+        self
+    }
+
+    /// Adds the monitor layout of a multi-monitor session to the `SessionDto`.
+    ///
+    /// # Arguments
+    /// * `monitors` - The monitors of the layout.
+    ///
+    /// # Returns
+    /// The `SessionDto` including the monitor layout.
+    pub fn with_monitors(mut self, monitors: Vec<Monitor>) -> Self {
+        self.monitors = monitors;
+        self
+    }
+
+    /// Adds the DPI and color depth of the display to the `SessionDto`.
+    ///
+    /// # Arguments
+    /// * `dpi` - The resolution of the display in dots per inch.
+    /// * `depth` - The color depth of the display in bits per pixel.
+    ///
+    /// # Returns
+    /// The `SessionDto` including the display properties.
+    pub fn with_display_properties(mut self, dpi: u32, depth: u32) -> Self {
+        self.dpi = dpi;
+        self.depth = depth;
+        self
+    }
+
+    /// Adds the name of the session profile to the `SessionDto`.
+    ///
+    /// # Arguments
+    /// * `profile` - The name of the session profile.
+    ///
+    /// # Returns
+    /// The `SessionDto` including the profile.
+    pub fn with_profile(mut self, profile: String) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Adds the labels of the session to the `SessionDto`.
+    ///
+    /// # Arguments
+    /// * `labels` - The keys and values of the labels.
+    ///
+    /// # Returns
+    /// The `SessionDto` including the labels.
+    pub fn with_labels(mut self, labels: HashMap<String, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Flags the `SessionDto` with the reason its user no longer qualifies for the session.
+    ///
+    /// # Arguments
+    /// * `revalidation_failure` - The reason given by the revalidation.
+    ///
+    /// # Returns
+    /// The flagged `SessionDto`.
+    pub fn with_revalidation_failure(mut self, revalidation_failure: String) -> Self {
+        self.revalidation_failure = Some(revalidation_failure);
+        self
+    }
+
     /// Returns the session ID.
     pub fn id(&self) -> &str {
         &self.id
@@ -100,6 +250,61 @@ impl SessionDto {
     pub fn resolution(&self) -> String {
         format!("{}x{}", self.width, self.height)
     }
+
+    /// Returns the display server backend.
+    pub fn display_server(&self) -> &str {
+        &self.display_server
+    }
+
+    /// Returns the unix timestamp at which the session was created.
+    pub fn created(&self) -> i64 {
+        self.created
+    }
+
+    /// Returns the monitors of a multi-monitor session, empty for a single-monitor session.
+    pub fn monitors(&self) -> &[Monitor] {
+        &self.monitors
+    }
+
+    /// Returns the DPI of the display.
+    pub fn dpi(&self) -> u32 {
+        self.dpi
+    }
+
+    /// Returns the color depth of the display.
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// Returns the name of the session profile, if any.
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Returns the labels of the session.
+    pub fn labels(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+
+    /// Returns the cookie of the Xauthority file of the session, if it was returned.
+    pub fn cookie(&self) -> Option<&str> {
+        self.cookie.as_deref()
+    }
+
+    /// Returns the access to the session granted to other users.
+    pub fn grants(&self) -> &[GrantDto] {
+        &self.grants
+    }
+
+    /// Returns the reconnection token, if one was issued.
+    pub fn reconnection_token(&self) -> Option<&str> {
+        self.reconnection_token.as_deref()
+    }
+
+    /// Returns the reason the user no longer qualifies for the session, if it has been flagged.
+    pub fn revalidation_failure(&self) -> Option<&str> {
+        self.revalidation_failure.as_deref()
+    }
 }
 
 impl fmt::Display for SessionDto {
@@ -115,6 +320,14 @@ impl fmt::Display for SessionDto {
             .field("xauthority_file_path", &self.xauthority_file_path)
             .field("width", &self.width)
             .field("height", &self.height)
+            .field("display_server", &self.display_server)
+            .field("created", &self.created)
+            .field("dpi", &self.dpi)
+            .field("depth", &self.depth)
+            .field("monitors", &self.monitors.iter().map(Monitor::to_string).collect::<Vec<String>>())
+            .field("profile", &self.profile)
+            .field("labels", &self.labels)
+            .field("grants", &self.grants.iter().map(GrantDto::to_string).collect::<Vec<String>>())
             .finish()
     }
 }
@@ -136,7 +349,7 @@ impl From<&Session> for SessionDto {
         let xauthority_file_path = session.xauthority_file_path();
         let id  = session.id().simple();
         let (width, height) = session.resolution().split();
-        SessionDto::new(
+        let session_dto = SessionDto::new(
             id.to_string(),
             username.into(),
             uid,
@@ -145,7 +358,17 @@ impl From<&Session> for SessionDto {
             window_manager_process_id,
             xauthority_file_path.into(),
             width,
-            height
+            height,
+            session.display_server().to_string(),
         )
+            .with_created(session.created())
+            .with_monitors(session.resolution().monitors().to_vec())
+            .with_grants(session.grants().iter().map(GrantDto::from).collect())
+            .with_labels(session.labels().clone())
+            .with_display_properties(session.display_properties().dpi(), session.display_properties().depth());
+        match session.profile() {
+            Some(profile) => session_dto.with_profile(profile.into()),
+            None => session_dto,
+        }
     }
 }
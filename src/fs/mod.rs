@@ -5,6 +5,10 @@ use std::os::unix::fs::PermissionsExt;
 
 use crate::common::ApplicationError;
 
+pub use xauthority::write_xauthority;
+
+mod xauthority;
+
 /// Changes the ownership of a file or directory.
 ///
 /// # Arguments
@@ -0,0 +1,174 @@
+use std::convert::TryFrom;
+use std::ffi::CStr;
+use std::fs::{self, OpenOptions, Permissions};
+use std::io::Write;
+use std::os::unix::fs::{fchown, OpenOptionsExt, PermissionsExt};
+
+use crate::common::ApplicationError;
+
+/// The address family of the entries bound to the local host, identified by its hostname.
+const FAMILY_LOCAL: u16 = 256;
+
+/// The address family of the entries matching any address.
+const FAMILY_WILD: u16 = 65535;
+
+/// The name of the authorization protocol of the cookies.
+const MIT_MAGIC_COOKIE: &str = "MIT-MAGIC-COOKIE-1";
+
+/// Writes an Xauthority file containing the MIT-MAGIC-COOKIE-1 cookie of a display, replacing any existing file.
+/// The file contains an entry for the local hostname (as written by `xauth add`) and a wildcard entry, which is
+/// still matched if the hostname changes.
+///
+/// The entries are written to a temporary file with the given owner and mode, which is then renamed, so that
+/// the file is never read partially written.
+///
+/// # Arguments
+/// * `path` - The path to the Xauthority file.
+/// * `display` - The display number.
+/// * `cookie` - The cookie.
+/// * `uid` - The user ID to set as the owner.
+/// * `gid` - The group ID to set as the owner.
+/// * `mode` - The permissions of the file.
+///
+/// # Returns
+/// A `Result` indicating success or an `ApplicationError` if the file cannot be written.
+pub fn write_xauthority(path: &str, display: u32, cookie: &[u8], uid: u32, gid: u32, mode: u32) -> Result<(), ApplicationError> {
+    let display = display.to_string();
+    let mut data = vec![];
+    if let Some(hostname) = hostname() {
+        encode_entry(&mut data, FAMILY_LOCAL, hostname.as_bytes(), &display, cookie)?;
+    }
+    encode_entry(&mut data, FAMILY_WILD, &[], &display, cookie)?;
+
+    let temporary_path = format!("{}.tmp", path);
+    let result = write_file(&temporary_path, &data, uid, gid, mode).and_then(|_| fs::rename(&temporary_path, path));
+    if let Err(error) = result {
+        // the temporary file may not exist if it could not be created
+        let _ = fs::remove_file(&temporary_path);
+        return Err(ApplicationError::environment(format!("Could not write Xauthority file {}: {}", path, error)));
+    }
+    Ok(())
+}
+
+/// Writes data to a new file with the given owner and mode, and flushes it to the disk.
+fn write_file(path: &str, data: &[u8], uid: u32, gid: u32, mode: u32) -> std::io::Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(mode)
+        .open(path)?;
+    // the mode given at creation is restricted by the umask
+    file.set_permissions(Permissions::from_mode(mode))?;
+    fchown(&file, Some(uid), Some(gid))?;
+    file.write_all(data)?;
+    file.sync_all()
+}
+
+/// Encodes an Xauthority entry: its family, followed by its address, display number, protocol name and cookie,
+/// each prefixed with its length. All the integers are big-endian.
+fn encode_entry(data: &mut Vec<u8>, family: u16, address: &[u8], display: &str, cookie: &[u8]) -> Result<(), ApplicationError> {
+    data.extend_from_slice(&family.to_be_bytes());
+    for field in [address, display.as_bytes(), MIT_MAGIC_COOKIE.as_bytes(), cookie] {
+        let length = u16::try_from(field.len())
+            .map_err(|_| ApplicationError::environment("Xauthority entry field is too long"))?;
+        data.extend_from_slice(&length.to_be_bytes());
+        data.extend_from_slice(field);
+    }
+    Ok(())
+}
+
+/// Retrieves the hostname of the local host.
+fn hostname() -> Option<String> {
+    let mut buffer = [0u8; 256];
+    if unsafe { libc::gethostname(buffer.as_mut_ptr() as *mut libc::c_char, buffer.len()) } != 0 {
+        return None;
+    }
+    CStr::from_bytes_until_nul(&buffer)
+        .ok()
+        .map(|hostname| hostname.to_string_lossy().into_owned())
+        .filter(|hostname| !hostname.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+    use std::path::Path;
+
+    use super::*;
+
+    /// The cookie of the fixtures.
+    const COOKIE: [u8; 16] = [0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+
+    /// Created with `xauth -f local.Xauthority add testhost/unix:60 MIT-MAGIC-COOKIE-1 00112233445566778899aabbccddeeff`.
+    const LOCAL_FIXTURE: &[u8] = include_bytes!("testdata/local.Xauthority");
+
+    /// Created with `xauth -f wild.Xauthority nmerge -` from the entry
+    /// `ffff 0000 0002 3630 0012 4d49542d4d414749432d434f4f4b49452d31 0010 00112233445566778899aabbccddeeff`.
+    const WILD_FIXTURE: &[u8] = include_bytes!("testdata/wild.Xauthority");
+
+    /// Creates an empty directory for a test in the temporary directory.
+    fn test_directory(name: &str) -> String {
+        let directory = std::env::temp_dir().join(format!("webx-xauthority-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).unwrap();
+        directory.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn encodes_local_entry_as_xauth() {
+        let mut data = vec![];
+        encode_entry(&mut data, FAMILY_LOCAL, b"testhost", "60", &COOKIE).unwrap();
+        assert_eq!(data, LOCAL_FIXTURE);
+    }
+
+    #[test]
+    fn encodes_wild_entry_as_xauth() {
+        let mut data = vec![];
+        encode_entry(&mut data, FAMILY_WILD, &[], "60", &COOKIE).unwrap();
+        assert_eq!(data, WILD_FIXTURE);
+    }
+
+    #[test]
+    fn rejects_too_long_fields() {
+        let mut data = vec![];
+        let address = vec![b'a'; u16::MAX as usize + 1];
+        assert!(encode_entry(&mut data, FAMILY_LOCAL, &address, "60", &COOKIE).is_err());
+    }
+
+    #[test]
+    fn writes_file_with_mode() {
+        let directory = test_directory("mode");
+        let path = format!("{}/test.Xauthority", directory);
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+
+        write_xauthority(&path, 60, &COOKIE, uid, gid, 0o640).unwrap();
+
+        let metadata = fs::metadata(&path).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o640);
+        assert_eq!(metadata.uid(), uid);
+        assert!(fs::read(&path).unwrap().ends_with(WILD_FIXTURE));
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn replaces_existing_file() {
+        let directory = test_directory("replace");
+        let path = format!("{}/test.Xauthority", directory);
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+        fs::write(&path, b"previous content").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let previous_inode = fs::metadata(&path).unwrap().ino();
+
+        write_xauthority(&path, 60, &COOKIE, uid, gid, 0o600).unwrap();
+
+        let metadata = fs::metadata(&path).unwrap();
+        // the file is replaced by the renamed temporary file rather than rewritten in place
+        assert_ne!(metadata.ino(), previous_inode);
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        assert!(fs::read(&path).unwrap().ends_with(WILD_FIXTURE));
+        assert!(!Path::new(&format!("{}.tmp", path)).exists());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -2,6 +2,11 @@
 extern crate log;
 extern crate pam_client2 as pam_client;
 
+/// Logs a message to the audit log: the `audit` log target, which can be written to a dedicated file.
+macro_rules! audit {
+    ($($arg:tt)+) => (info!(target: "audit", $($arg)+))
+}
+
 pub mod authentication;
 pub mod common;
 pub mod fs;
@@ -1,6 +1,8 @@
 use prettytable::{Cell, Row, Table};
 
-use crate::{authentication::{Credentials}, common::{ApplicationError, Request, Response, ScreenResolution}};
+use crate::dto::DrainDto;
+
+use crate::{authentication::{Credentials}, common::{ApplicationError, Notification, Request, Response, ScreenResolution, SessionFilter, SessionOptions}};
 
 /// The `Client` struct provides functionality for interacting with the WebX Session Manager server,
 /// including sending requests and handling responses.
@@ -27,14 +29,17 @@ impl Client {
         })
     }
 
-    /// Retrieves a list of all active sessions.
+    /// Retrieves a list of the active sessions.
+    ///
+    /// # Arguments
+    /// * `filter` - The criteria of the sessions to list.
     ///
     /// # Returns
     /// A `Result` indicating success or an `ApplicationError`.
-    pub fn who(&self) -> Result<(), ApplicationError> {
+    pub fn who(&self, filter: SessionFilter) -> Result<(), ApplicationError> {
         println!("Fetching a list of sessions");
 
-        if let Ok(response) = self.send(Request::Who) {
+        if let Ok(response) = self.send(Request::Who(Some(filter))) {
             match response {
                 Response::Who(sessions) => {
                     let mut table = Table::new();
@@ -44,17 +49,77 @@ impl Client {
                         Cell::new("Xorg PID"),
                         Cell::new("Window Manager PID"),
                         Cell::new("User"),
-                        Cell::new("XAuthority")
+                        Cell::new("XAuthority"),
+                        Cell::new("Grants"),
+                        Cell::new("Flagged")
                     ]));
 
                     for session in sessions {
                         table.add_row(Row::new(vec![
                             Cell::new(session.id()),
-                            Cell::new(session.display_id()),
+                            Cell::new(&format!("{} ({})", session.display_id(), session.display_server())),
                             Cell::new(&session.xorg_process_id().to_string()),
                             Cell::new(&session.window_manager_process_id().to_string()),
                             Cell::new(&format!("{} ({})", session.username(), &session.uid())),
-                            Cell::new(session.xauthority_file_path())
+                            Cell::new(session.xauthority_file_path()),
+                            Cell::new(&session.grants().iter().map(|grant| grant.to_string()).collect::<Vec<String>>().join(", ")),
+                            Cell::new(session.revalidation_failure().unwrap_or_default())
+                        ]));
+                    }
+
+                    table.printstd();
+                }
+                Response::Error { message } => println!("Got an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves a single session.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn get(&self, id: String) -> Result<(), ApplicationError> {
+        println!("Fetching session {}", id);
+
+        if let Ok(response) = self.send(Request::Get { id }) {
+            match response {
+                Response::Get(session) => println!("Session: {}", session),
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lists the desktop environments that can be chosen at login.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn desktops(&self) -> Result<(), ApplicationError> {
+        println!("Fetching a list of desktops");
+
+        if let Ok(response) = self.send(Request::Desktops) {
+            match response {
+                Response::Desktops(desktops) => {
+                    let mut table = Table::new();
+                    table.add_row(Row::new(vec![
+                        Cell::new("ID"),
+                        Cell::new("Name"),
+                        Cell::new("Description"),
+                    ]));
+
+                    for desktop in desktops {
+                        table.add_row(Row::new(vec![
+                            Cell::new(desktop.id()),
+                            Cell::new(desktop.name()),
+                            Cell::new(desktop.comment()),
                         ]));
                     }
 
@@ -73,23 +138,147 @@ impl Client {
     /// # Arguments
     /// * `credentials` - The user's credentials.
     /// * `resolution` - The screen resolution for the session.
+    /// * `options` - The display server, desktop, profile, display properties, keyboard, locale, timezone, environment and labels, if any.
     ///
     /// # Returns
     /// A `Result` indicating success or an `ApplicationError`.
-    pub fn login(&self, credentials: Credentials, resolution: ScreenResolution) -> Result<(), ApplicationError> {
+    pub fn login(&self, credentials: Credentials, resolution: ScreenResolution, options: SessionOptions) -> Result<(), ApplicationError> {
         println!("Logging in user: {}", credentials.username());
 
         let request = Request::Login {
             username: credentials.username().into(),
             password: credentials.password().into(),
             width: resolution.width(),
-            height: resolution.height()
+            height: resolution.height(),
+            otp: credentials.otp().map(String::from),
+            display_server: options.display_server().map(String::from),
+            desktop: options.desktop().map(String::from),
+            profile: options.profile().map(String::from),
+            monitors: Some(resolution.monitors().to_vec()).filter(|monitors| !monitors.is_empty()),
+            dpi: options.dpi(),
+            depth: options.depth(),
+            keyboard: options.keyboard().cloned(),
+            locale: options.locale().map(String::from),
+            timezone: options.timezone().map(String::from),
+            environment: Some(options.environment().clone()).filter(|environment| !environment.is_empty()),
+            labels: Some(options.labels().clone()).filter(|labels| !labels.is_empty()),
+        };
+        if let Ok(response) = self.send(request) {
+            match response {
+                Response::Login(session) => {
+                    println!("Session launched: {}", session);
+                    if let Some(token) = session.reconnection_token() {
+                        println!("Reconnection token: {}", token);
+                    }
+                }
+                Response::LockedOut { message } => println!("Login rejected: {}", message),
+                Response::Draining { message } => println!("Login rejected: {}", message),
+                Response::InvalidResolution { message } => println!("Resolution rejected: {}", message),
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Logs in a pre-authenticated user with a signed login ticket and creates a new session.
+    ///
+    /// # Arguments
+    /// * `ticket` - The signed login ticket.
+    /// * `resolution` - The screen resolution for the session.
+    /// * `options` - The display server, desktop, profile, display properties, keyboard, locale, timezone, environment and labels, if any.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn login_with_ticket(&self, ticket: String, resolution: ScreenResolution, options: SessionOptions) -> Result<(), ApplicationError> {
+        println!("Logging in user with a login ticket");
+
+        let request = Request::LoginTicket {
+            ticket,
+            width: resolution.width(),
+            height: resolution.height(),
+            display_server: options.display_server().map(String::from),
+            desktop: options.desktop().map(String::from),
+            profile: options.profile().map(String::from),
+            monitors: Some(resolution.monitors().to_vec()).filter(|monitors| !monitors.is_empty()),
+            dpi: options.dpi(),
+            depth: options.depth(),
+            keyboard: options.keyboard().cloned(),
+            locale: options.locale().map(String::from),
+            timezone: options.timezone().map(String::from),
+            environment: Some(options.environment().clone()).filter(|environment| !environment.is_empty()),
+            labels: Some(options.labels().clone()).filter(|labels| !labels.is_empty()),
         };
         if let Ok(response) = self.send(request) {
             match response {
                 Response::Login(session) => {
                     println!("Session launched: {}", session);
+                    if let Some(token) = session.reconnection_token() {
+                        println!("Reconnection token: {}", token);
+                    }
+                }
+                Response::Draining { message } => println!("Login rejected: {}", message),
+                Response::InvalidResolution { message } => println!("Resolution rejected: {}", message),
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves an existing session using a reconnection token.
+    ///
+    /// # Arguments
+    /// * `token` - The reconnection token returned by a previous login.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn reconnect(&self, token: String) -> Result<(), ApplicationError> {
+        println!("Reconnecting to session");
+
+        let request = Request::Reconnect {
+            token
+        };
+        if let Ok(response) = self.send(request) {
+            match response {
+                Response::Reconnect(session) => {
+                    println!("Reconnected to session: {}", session);
+                    if let Some(token) = session.reconnection_token() {
+                        println!("Reconnection token: {}", token);
+                    }
                 }
+                Response::Draining { message } => println!("Reconnection rejected: {}", message),
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Changes the screen resolution of a running session.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session to resize.
+    /// * `resolution` - The new screen resolution.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn resize(&self, id: String, resolution: ScreenResolution) -> Result<(), ApplicationError> {
+        println!("Resizing session {} to {}", id, resolution);
+
+        let request = Request::Resize {
+            id,
+            width: resolution.width(),
+            height: resolution.height(),
+            monitors: Some(resolution.monitors().to_vec()).filter(|monitors| !monitors.is_empty()),
+        };
+        if let Ok(response) = self.send(request) {
+            match response {
+                Response::Resize(session) => println!("Session resized: {}", session),
+                Response::InvalidResolution { message } => println!("Resolution rejected: {}", message),
                 Response::Error { message } => println!("Received an error response: {}", message),
                 _ => println!("Received an unknown response")
             }
@@ -124,6 +313,363 @@ impl Client {
         Ok(())
     }
 
+    /// Logs out all the sessions matching a filter.
+    ///
+    /// # Arguments
+    /// * `filter` - The criteria of the sessions to log out.
+    /// * `all` - Whether all the sessions are logged out when the filter has no criteria.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn logout_sessions(&self, filter: SessionFilter, all: bool) -> Result<(), ApplicationError> {
+        println!("Logging out sessions");
+
+        if let Ok(response) = self.send(Request::LogoutSessions { filter, all }) {
+            match response {
+                Response::LogoutSessions(results) => {
+                    let mut table = Table::new();
+                    table.add_row(Row::new(vec![
+                        Cell::new("Id"),
+                        Cell::new("User"),
+                        Cell::new("Result"),
+                    ]));
+
+                    for result in results {
+                        let status = match result.error() {
+                            Some(error) => error.to_string(),
+                            None => "logged out".to_string(),
+                        };
+                        table.add_row(Row::new(vec![
+                            Cell::new(result.id()),
+                            Cell::new(result.username()),
+                            Cell::new(&status),
+                        ]));
+                    }
+
+                    table.printstd();
+                }
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves the list of usernames that are locked out after too many failed login attempts.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn lockouts(&self) -> Result<(), ApplicationError> {
+        println!("Fetching a list of lockouts");
+
+        if let Ok(response) = self.send(Request::Lockouts) {
+            match response {
+                Response::Lockouts(lockouts) => {
+                    let mut table = Table::new();
+                    table.add_row(Row::new(vec![
+                        Cell::new("User"),
+                        Cell::new("Lockouts"),
+                        Cell::new("Locked until"),
+                    ]));
+
+                    for lockout in lockouts {
+                        let locked_until = chrono::DateTime::from_timestamp(lockout.locked_until(), 0)
+                            .map(|locked_until| locked_until.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                            .unwrap_or_default();
+                        table.add_row(Row::new(vec![
+                            Cell::new(lockout.username()),
+                            Cell::new(&lockout.lockouts().to_string()),
+                            Cell::new(&locked_until),
+                        ]));
+                    }
+
+                    table.printstd();
+                }
+                Response::Error { message } => println!("Got an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Clears the lockout of a username, or of all usernames.
+    ///
+    /// # Arguments
+    /// * `username` - The username to clear, or `None` to clear all usernames.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn clear_lockouts(&self, username: Option<String>) -> Result<(), ApplicationError> {
+        println!("Clearing lockouts for {}", username.as_deref().unwrap_or("all users"));
+
+        if let Ok(response) = self.send(Request::ClearLockouts { username }) {
+            match response {
+                Response::ClearLockouts { cleared } => println!("Cleared {} lockouts", cleared),
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Enrolls a user in the TOTP second factor and prints the otpauth URI to register in an authenticator application.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the user to enroll.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn totp_enroll(&self, username: String) -> Result<(), ApplicationError> {
+        println!("Enrolling user {} for TOTP", username);
+
+        if let Ok(response) = self.send(Request::TotpEnroll { username }) {
+            match response {
+                Response::TotpEnroll { uri } => println!("TOTP URI: {}", uri),
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes the TOTP enrollment of a user.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the user.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn totp_reset(&self, username: String) -> Result<(), ApplicationError> {
+        println!("Resetting TOTP enrollment of user {}", username);
+
+        if let Ok(response) = self.send(Request::TotpReset { username }) {
+            match response {
+                Response::TotpReset { reset: true } => println!("TOTP enrollment removed"),
+                Response::TotpReset { reset: false } => println!("User is not enrolled for TOTP"),
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Grants another user access to a session and prints the Xauthority file and cookie of the grantee.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    /// * `grantee` - The username of the user granted access.
+    /// * `view_only` - Whether the grantee may only view the session.
+    /// * `cookie` - The cookie of the session, required unless the request is sent to the admin socket.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn grant(&self, id: String, grantee: String, view_only: bool, cookie: Option<String>) -> Result<(), ApplicationError> {
+        println!("Granting user {} access to session {}", grantee, id);
+
+        if let Ok(response) = self.send(Request::Grant { id, grantee, view_only, cookie }) {
+            match response {
+                Response::Grant(grant) => {
+                    println!("Access granted: {} with XAuthority {}", grant, grant.xauthority_file_path());
+                    if let Some(cookie) = grant.cookie() {
+                        println!("Cookie: {}", cookie);
+                    }
+                }
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Revokes the access to a session granted to a user.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    /// * `grantee` - The username of the user granted access.
+    /// * `cookie` - The cookie of the session, required unless the request is sent to the admin socket.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn revoke_grant(&self, id: String, grantee: String, cookie: Option<String>) -> Result<(), ApplicationError> {
+        println!("Revoking the access of user {} to session {}", grantee, id);
+
+        if let Ok(response) = self.send(Request::RevokeGrant { id, grantee, cookie }) {
+            match response {
+                Response::RevokeGrant { revoked: true } => println!("Access revoked"),
+                Response::RevokeGrant { revoked: false } => println!("User was not granted access to the session"),
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Replaces the cookie given to the clients of a session, disconnecting those using the previous cookie.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn rotate_cookie(&self, id: String) -> Result<(), ApplicationError> {
+        println!("Rotating the cookie of session {}", id);
+
+        if let Ok(response) = self.send(Request::RotateCookie { id }) {
+            match response {
+                Response::RotateCookie(session) => {
+                    println!("Cookie rotated: {}", session);
+                    if let Some(cookie) = session.cookie() {
+                        println!("Cookie: {}", cookie);
+                    }
+                }
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Enables the drain mode: new logins are rejected while the existing sessions keep running.
+    ///
+    /// # Arguments
+    /// * `allow_existing` - Whether the users that already have a session can still log in to it.
+    /// * `deadline` - The unix timestamp at which the remaining sessions are terminated, if any.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn drain(&self, allow_existing: bool, deadline: Option<i64>) -> Result<(), ApplicationError> {
+        println!("Draining the host");
+
+        if let Ok(response) = self.send(Request::Drain { allow_existing, deadline }) {
+            match response {
+                Response::Drain(drain) => self.print_drain(&drain),
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Disables the drain mode.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn undrain(&self) -> Result<(), ApplicationError> {
+        println!("Stopping draining the host");
+
+        if let Ok(response) = self.send(Request::Undrain) {
+            match response {
+                Response::Undrain(drain) => self.print_drain(&drain),
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves the drain mode.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn drain_status(&self) -> Result<(), ApplicationError> {
+        println!("Fetching the drain mode");
+
+        if let Ok(response) = self.send(Request::DrainStatus) {
+            match response {
+                Response::DrainStatus(drain) => self.print_drain(&drain),
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Shows a desktop notification in sessions.
+    ///
+    /// # Arguments
+    /// * `ids` - The IDs of the sessions, if the notification is only shown in these sessions.
+    /// * `filter` - The criteria of the sessions.
+    /// * `all` - Whether the notification is shown in all the sessions when no IDs or criteria are given.
+    /// * `notification` - The notification.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn notify(&self, ids: Vec<String>, filter: SessionFilter, all: bool, notification: Notification) -> Result<(), ApplicationError> {
+        println!("Notifying sessions");
+
+        let request = Request::Notify {
+            ids,
+            filter,
+            all,
+            summary: notification.summary().into(),
+            body: notification.body().map(String::from),
+            urgency: notification.urgency(),
+        };
+        if let Ok(response) = self.send(request) {
+            match response {
+                Response::Notify(results) => {
+                    let mut table = Table::new();
+                    table.add_row(Row::new(vec![
+                        Cell::new("Id"),
+                        Cell::new("User"),
+                        Cell::new("Result"),
+                    ]));
+
+                    for result in results {
+                        let status = match result.error() {
+                            Some(error) => error.to_string(),
+                            None => "notified".to_string(),
+                        };
+                        table.add_row(Row::new(vec![
+                            Cell::new(result.id()),
+                            Cell::new(result.username()),
+                            Cell::new(&status),
+                        ]));
+                    }
+
+                    table.printstd();
+                }
+                Response::Error { message } => println!("Received an error response: {}", message),
+                _ => println!("Received an unknown response")
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prints the drain mode.
+    ///
+    /// # Arguments
+    /// * `drain` - The drain mode.
+    fn print_drain(&self, drain: &DrainDto) {
+        if !drain.is_draining() {
+            println!("The host is not draining ({} sessions)", drain.sessions());
+            return;
+        }
+
+        let format = |timestamp: i64| chrono::DateTime::from_timestamp(timestamp, 0)
+            .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        println!("The host is draining since {} ({} sessions)", drain.started().map(format).unwrap_or_default(), drain.sessions());
+        if drain.allow_existing() {
+            println!("The users that already have a session can still log in to it");
+        }
+        if let Some(deadline) = drain.deadline() {
+            println!("The remaining sessions will be terminated at {}", format(deadline));
+        }
+    }
+
     /// Sends a request to the WebX Session Manager server and receives a response.
     ///
     /// # Arguments
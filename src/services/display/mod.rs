@@ -0,0 +1,194 @@
+pub use security::SecurityClient;
+pub use xdummy::Xdummy;
+pub use xorgxrdp::XorgXrdp;
+pub use xvfb::Xvfb;
+pub use xvnc::Xvnc;
+
+use std::process::Command;
+
+use crate::common::{ApplicationError, DisplayProperties, DisplayServerKind, Monitor, ScreenResolution, XorgSettings};
+
+mod security;
+mod xdummy;
+mod xorgxrdp;
+mod xvfb;
+mod xvnc;
+
+/// The `DisplayServer` trait describes a display server backend that can be launched for a session:
+/// the program to run, its arguments and environment, and how the screen resolution is applied.
+pub trait DisplayServer: Send + Sync {
+    /// Returns the kind of the display server.
+    fn kind(&self) -> DisplayServerKind;
+
+    /// Returns the program to execute.
+    fn program(&self) -> &str;
+
+    /// Builds the arguments of the display server.
+    ///
+    /// # Arguments
+    /// * `display` - The X11 display (for example `:60`).
+    /// * `authority_file_path` - The path to the Xauthority file of the session.
+    /// * `runtime_path` - The runtime directory of the session user.
+    /// * `resolution` - The screen resolution for the session.
+    /// * `properties` - The DPI and color depth of the display.
+    ///
+    /// # Returns
+    /// A vector of arguments.
+    fn arguments(&self, display: &str, authority_file_path: &str, runtime_path: &str, resolution: &ScreenResolution, properties: &DisplayProperties) -> Vec<String>;
+
+    /// Builds the environment variables specific to the display server.
+    ///
+    /// # Arguments
+    /// * `resolution` - The screen resolution for the session.
+    ///
+    /// # Returns
+    /// A vector of environment variable names and values.
+    fn environment(&self, _resolution: &ScreenResolution) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    /// Applies the screen resolution once the display server has started, for display servers that cannot be
+    /// started with it.
+    ///
+    /// # Arguments
+    /// * `display` - The X11 display.
+    /// * `authority_file_path` - The path to the Xauthority file of the session.
+    /// * `resolution` - The screen resolution for the session.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    fn apply_resolution(&self, _display: &str, _authority_file_path: &str, _resolution: &ScreenResolution) -> Result<(), ApplicationError> {
+        Ok(())
+    }
+
+    /// Changes the screen resolution of a running display server.
+    ///
+    /// # Arguments
+    /// * `display` - The X11 display.
+    /// * `authority_file_path` - The path to the Xauthority file of the session.
+    /// * `resolution` - The new screen resolution.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError` if the display server cannot be resized.
+    fn resize(&self, _display: &str, _authority_file_path: &str, _resolution: &ScreenResolution) -> Result<(), ApplicationError> {
+        Err(ApplicationError::session(format!("The {} display server does not support resizing", self.kind())))
+    }
+
+    /// Defines the monitors of a multi-monitor screen layout as RandR monitors, replacing the previous ones.
+    ///
+    /// # Arguments
+    /// * `display` - The X11 display.
+    /// * `authority_file_path` - The path to the Xauthority file of the session.
+    /// * `monitors` - The monitors of the layout.
+    /// * `previous` - The monitors previously defined for the session, if any.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    fn apply_monitors(&self, display: &str, authority_file_path: &str, monitors: &[Monitor], previous: &[Monitor]) -> Result<(), ApplicationError> {
+        set_monitors(display, authority_file_path, monitors, previous)
+    }
+}
+
+/// Sets the screen (framebuffer) size of a display using RandR with `xrandr`.
+///
+/// # Arguments
+/// * `display` - The X11 display.
+/// * `authority_file_path` - The path to the Xauthority file of the session.
+/// * `resolution` - The screen resolution.
+///
+/// # Returns
+/// A `Result` indicating success or an `ApplicationError`.
+fn set_screen_size(display: &str, authority_file_path: &str, resolution: &ScreenResolution) -> Result<(), ApplicationError> {
+    debug!("Setting the resolution of display {} to {}", display, resolution);
+    let output = Command::new("xrandr")
+        .arg("--fb")
+        .arg(resolution.to_string())
+        .env_clear()
+        .env("DISPLAY", display)
+        .env("XAUTHORITY", authority_file_path)
+        .output()?;
+
+    if !output.status.success() {
+        return Err(ApplicationError::session(format!(
+            "Failed to set the resolution of display {} to {}: {}",
+            display,
+            resolution,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Defines RandR monitors with `xrandr`. The monitors are named WEBX-0, WEBX-1... and are not bound to an output,
+/// their physical size being derived from their size in pixels at 96 DPI.
+///
+/// # Arguments
+/// * `display` - The X11 display.
+/// * `authority_file_path` - The path to the Xauthority file of the session.
+/// * `monitors` - The monitors to define.
+/// * `previous` - The monitors to delete first.
+///
+/// # Returns
+/// A `Result` indicating success or an `ApplicationError`.
+fn set_monitors(display: &str, authority_file_path: &str, monitors: &[Monitor], previous: &[Monitor]) -> Result<(), ApplicationError> {
+    let mut arguments: Vec<String> = vec![];
+    for index in 0..previous.len() {
+        arguments.push("--delmonitor".into());
+        arguments.push(format!("WEBX-{}", index));
+    }
+    for (index, monitor) in monitors.iter().enumerate() {
+        // the monitor name is prefixed with * to make it the primary monitor
+        let name = format!("{}WEBX-{}", if monitor.is_primary() { "*" } else { "" }, index);
+        let geometry = format!(
+            "{}/{}x{}/{}+{}+{}",
+            monitor.width(),
+            monitor.width() as u64 * 254 / 960,
+            monitor.height(),
+            monitor.height() as u64 * 254 / 960,
+            monitor.x(),
+            monitor.y()
+        );
+        arguments.push("--setmonitor".into());
+        arguments.push(name);
+        arguments.push(geometry);
+        arguments.push("none".into());
+    }
+    if arguments.is_empty() {
+        return Ok(());
+    }
+
+    debug!("Setting the monitors of display {}: {:?}", display, arguments);
+    let output = Command::new("xrandr")
+        .args(&arguments)
+        .env_clear()
+        .env("DISPLAY", display)
+        .env("XAUTHORITY", authority_file_path)
+        .output()?;
+
+    if !output.status.success() {
+        return Err(ApplicationError::session(format!(
+            "Failed to set the monitors of display {}: {}",
+            display,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Creates the display server backend of a given kind.
+///
+/// # Arguments
+/// * `kind` - The kind of display server.
+/// * `settings` - The Xorg settings.
+/// * `config_path` - The Xorg configuration overriding the one of the settings (for example from a session profile), if any.
+///
+/// # Returns
+/// A boxed `DisplayServer`.
+pub fn create_display_server(kind: DisplayServerKind, settings: &XorgSettings, config_path: Option<&str>) -> Box<dyn DisplayServer> {
+    match kind {
+        DisplayServerKind::Xorgxrdp => Box::new(XorgXrdp::new(config_path.unwrap_or_else(|| settings.config_path()))),
+        DisplayServerKind::Xvfb => Box::new(Xvfb::new()),
+        DisplayServerKind::Xdummy => Box::new(Xdummy::new(config_path.unwrap_or_else(|| settings.xdummy_config_path()))),
+        DisplayServerKind::Xvnc => Box::new(Xvnc::new()),
+    }
+}
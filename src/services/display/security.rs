@@ -0,0 +1,213 @@
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
+
+use crate::common::ApplicationError;
+
+/// The name of the X11 authorization protocol used for the cookies.
+const MIT_MAGIC_COOKIE: &str = "MIT-MAGIC-COOKIE-1";
+
+/// The name of the X11 SECURITY extension.
+const SECURITY_EXTENSION: &str = "SECURITY";
+
+/// The opcode of the core QueryExtension request.
+const QUERY_EXTENSION: u8 = 98;
+
+/// The opcode of the core GetInputFocus request, used to wait for the requests that have no reply to be processed.
+const GET_INPUT_FOCUS: u8 = 43;
+
+/// The minor opcodes of the SECURITY extension requests.
+const SECURITY_QUERY_VERSION: u8 = 0;
+const SECURITY_GENERATE_AUTHORIZATION: u8 = 1;
+const SECURITY_REVOKE_AUTHORIZATION: u8 = 2;
+
+/// The value mask bits of the SecurityGenerateAuthorization request.
+const SECURITY_TIMEOUT: u32 = 1 << 0;
+const SECURITY_TRUST_LEVEL: u32 = 1 << 1;
+
+/// The `Authorization` struct represents an MIT-MAGIC-COOKIE-1 authorization generated by an X server
+/// with the SECURITY extension. Unlike the cookies of the authority file given to the server at startup,
+/// a generated authorization can be revoked while the server is running.
+pub struct Authorization {
+    id: u32,
+    cookie: Vec<u8>,
+}
+
+impl Authorization {
+    /// Returns the identifier of the authorization in the X server.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the cookie of the authorization.
+    pub fn cookie(&self) -> &[u8] {
+        &self.cookie
+    }
+}
+
+/// The `SecurityClient` struct is a minimal X11 client, connected to the unix socket of a display, that
+/// generates and revokes authorizations with the SECURITY extension.
+pub struct SecurityClient {
+    stream: UnixStream,
+    major_opcode: u8,
+}
+
+impl SecurityClient {
+    /// Connects to a display using its server cookie and verifies that it supports the SECURITY extension.
+    ///
+    /// # Arguments
+    /// * `socket_path` - The path to the unix socket of the display (for example `/tmp/.X11-unix/X60`).
+    /// * `cookie` - The MIT-MAGIC-COOKIE-1 cookie of the authority file given to the server.
+    ///
+    /// # Returns
+    /// A `Result` containing the `SecurityClient` or an `ApplicationError` if the connection fails.
+    pub fn connect(socket_path: &str, cookie: &[u8]) -> Result<Self, ApplicationError> {
+        let stream = UnixStream::connect(socket_path)?;
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+
+        let mut client = Self {
+            stream,
+            major_opcode: 0,
+        };
+        client.setup(cookie)?;
+        client.major_opcode = client.query_extension()?;
+        client.query_version()?;
+        Ok(client)
+    }
+
+    /// Generates a new authorization that never expires.
+    ///
+    /// # Arguments
+    /// * `trusted` - Whether the clients using the authorization are trusted (untrusted clients cannot access the resources of other clients).
+    ///
+    /// # Returns
+    /// A `Result` containing the `Authorization` or an `ApplicationError`.
+    pub fn generate(&mut self, trusted: bool) -> Result<Authorization, ApplicationError> {
+        let protocol = padded(MIT_MAGIC_COOKIE.as_bytes());
+        let mut request = vec![];
+        request.extend_from_slice(&(MIT_MAGIC_COOKIE.len() as u16).to_le_bytes());
+        // no authorization data: the server generates the cookie
+        request.extend_from_slice(&0u16.to_le_bytes());
+        request.extend_from_slice(&(SECURITY_TIMEOUT | SECURITY_TRUST_LEVEL).to_le_bytes());
+        request.extend_from_slice(&protocol);
+        // a timeout of 0 never expires the authorization
+        request.extend_from_slice(&0u32.to_le_bytes());
+        request.extend_from_slice(&(if trusted { 0u32 } else { 1u32 }).to_le_bytes());
+        self.send(self.major_opcode, SECURITY_GENERATE_AUTHORIZATION, &request)?;
+
+        let reply = self.receive_reply()?;
+        let id = u32::from_le_bytes([reply[8], reply[9], reply[10], reply[11]]);
+        let length = u16::from_le_bytes([reply[12], reply[13]]) as usize;
+        if reply.len() < 32 + length {
+            return Err(ApplicationError::session("Invalid SecurityGenerateAuthorization reply"));
+        }
+        Ok(Authorization {
+            id,
+            cookie: reply[32..32 + length].to_vec(),
+        })
+    }
+
+    /// Revokes an authorization. The clients connected with it are disconnected by the X server.
+    ///
+    /// # Arguments
+    /// * `id` - The identifier of the authorization.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    pub fn revoke(&mut self, id: u32) -> Result<(), ApplicationError> {
+        self.send(self.major_opcode, SECURITY_REVOKE_AUTHORIZATION, &id.to_le_bytes())?;
+        // the request has no reply: an error is received before the reply of the following request
+        self.send(GET_INPUT_FOCUS, 0, &[])?;
+        self.receive_reply()?;
+        Ok(())
+    }
+
+    /// Sends the connection setup with the cookie and verifies that the connection is accepted.
+    fn setup(&mut self, cookie: &[u8]) -> Result<(), ApplicationError> {
+        let mut setup = vec![b'l', 0];
+        setup.extend_from_slice(&11u16.to_le_bytes());
+        setup.extend_from_slice(&0u16.to_le_bytes());
+        setup.extend_from_slice(&(MIT_MAGIC_COOKIE.len() as u16).to_le_bytes());
+        setup.extend_from_slice(&(cookie.len() as u16).to_le_bytes());
+        setup.extend_from_slice(&[0, 0]);
+        setup.extend_from_slice(&padded(MIT_MAGIC_COOKIE.as_bytes()));
+        setup.extend_from_slice(&padded(cookie));
+        self.stream.write_all(&setup)?;
+
+        let mut header = [0u8; 8];
+        self.stream.read_exact(&mut header)?;
+        let length = u16::from_le_bytes([header[6], header[7]]) as usize * 4;
+        let mut data = vec![0u8; length];
+        self.stream.read_exact(&mut data)?;
+
+        if header[0] != 1 {
+            let reason_length = (header[1] as usize).min(data.len());
+            return Err(ApplicationError::session(format!(
+                "The X server refused the connection: {}",
+                String::from_utf8_lossy(&data[..reason_length])
+            )));
+        }
+        Ok(())
+    }
+
+    /// Retrieves the major opcode of the SECURITY extension.
+    fn query_extension(&mut self) -> Result<u8, ApplicationError> {
+        let mut request = vec![];
+        request.extend_from_slice(&(SECURITY_EXTENSION.len() as u16).to_le_bytes());
+        request.extend_from_slice(&[0, 0]);
+        request.extend_from_slice(&padded(SECURITY_EXTENSION.as_bytes()));
+        self.send(QUERY_EXTENSION, 0, &request)?;
+
+        let reply = self.receive_reply()?;
+        if reply[8] == 0 {
+            return Err(ApplicationError::session("The X server does not support the SECURITY extension"));
+        }
+        Ok(reply[9])
+    }
+
+    /// Negotiates the version of the SECURITY extension.
+    fn query_version(&mut self) -> Result<(), ApplicationError> {
+        let mut request = vec![];
+        request.extend_from_slice(&1u16.to_le_bytes());
+        request.extend_from_slice(&0u16.to_le_bytes());
+        self.send(self.major_opcode, SECURITY_QUERY_VERSION, &request)?;
+        self.receive_reply()?;
+        Ok(())
+    }
+
+    /// Sends a request: its opcode, a data byte (the minor opcode of extension requests), its length and its body.
+    fn send(&mut self, opcode: u8, data: u8, body: &[u8]) -> Result<(), ApplicationError> {
+        let mut request = vec![opcode, data];
+        request.extend_from_slice(&(((body.len() + 4) / 4) as u16).to_le_bytes());
+        request.extend_from_slice(body);
+        self.stream.write_all(&request)?;
+        Ok(())
+    }
+
+    /// Receives the next reply, skipping events and failing on errors.
+    fn receive_reply(&mut self) -> Result<Vec<u8>, ApplicationError> {
+        loop {
+            let mut reply = vec![0u8; 32];
+            self.stream.read_exact(&mut reply)?;
+            match reply[0] {
+                0 => return Err(ApplicationError::session(format!("The X server returned the error {} to request {}", reply[1], reply[10]))),
+                1 => {
+                    let length = u32::from_le_bytes([reply[4], reply[5], reply[6], reply[7]]) as usize * 4;
+                    let mut data = vec![0u8; length];
+                    self.stream.read_exact(&mut data)?;
+                    reply.extend_from_slice(&data);
+                    return Ok(reply);
+                }
+                _ => continue,
+            }
+        }
+    }
+}
+
+/// Pads data with zeros to a multiple of 4 bytes, as required by the X11 protocol.
+fn padded(data: &[u8]) -> Vec<u8> {
+    let mut padded = data.to_vec();
+    padded.resize(data.len().div_ceil(4) * 4, 0);
+    padded
+}
@@ -0,0 +1,65 @@
+use crate::common::{ApplicationError, DisplayProperties, DisplayServerKind, ScreenResolution};
+
+use super::{set_screen_size, DisplayServer};
+
+/// The `Xdummy` struct launches Xorg with the dummy video driver. The dummy driver starts with the
+/// resolution of its configuration, so the requested resolution is applied with `xrandr` once the server is running
+/// (within the virtual size declared in the configuration).
+pub struct Xdummy {
+    config_path: String,
+}
+
+impl Xdummy {
+    /// Creates a new `Xdummy` instance.
+    ///
+    /// # Arguments
+    /// * `config_path` - The path to the Xorg configuration using the dummy driver.
+    ///
+    /// # Returns
+    /// A new `Xdummy` instance.
+    pub fn new(config_path: &str) -> Self {
+        Self {
+            config_path: config_path.into(),
+        }
+    }
+}
+
+impl DisplayServer for Xdummy {
+    fn kind(&self) -> DisplayServerKind {
+        DisplayServerKind::Xdummy
+    }
+
+    fn program(&self) -> &str {
+        "Xorg"
+    }
+
+    fn arguments(&self, display: &str, authority_file_path: &str, _runtime_path: &str, _resolution: &ScreenResolution, properties: &DisplayProperties) -> Vec<String> {
+        vec![
+            display.into(),
+            "-auth".into(),
+            authority_file_path.into(),
+            "-config".into(),
+            self.config_path.clone(),
+            "-noreset".into(),
+            "-depth".into(),
+            properties.depth().to_string(),
+            "-dpi".into(),
+            properties.dpi().to_string(),
+            "-nolisten".into(),
+            "tcp".into(),
+            "-verbose".into(),
+        ]
+    }
+
+    fn environment(&self, _resolution: &ScreenResolution) -> Vec<(String, String)> {
+        vec![("XORG_RUN_AS_USER_OK".into(), "1".into())]
+    }
+
+    fn apply_resolution(&self, display: &str, authority_file_path: &str, resolution: &ScreenResolution) -> Result<(), ApplicationError> {
+        set_screen_size(display, authority_file_path, resolution)
+    }
+
+    fn resize(&self, display: &str, authority_file_path: &str, resolution: &ScreenResolution) -> Result<(), ApplicationError> {
+        set_screen_size(display, authority_file_path, resolution)
+    }
+}
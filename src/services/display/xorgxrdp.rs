@@ -0,0 +1,62 @@
+use crate::common::{ApplicationError, DisplayProperties, DisplayServerKind, ScreenResolution};
+
+use super::{set_screen_size, DisplayServer};
+
+/// The `XorgXrdp` struct launches Xorg with the xorgxrdp driver. The initial resolution is read by the
+/// driver from the `XRDP_START_WIDTH` and `XRDP_START_HEIGHT` environment variables.
+pub struct XorgXrdp {
+    config_path: String,
+}
+
+impl XorgXrdp {
+    /// Creates a new `XorgXrdp` instance.
+    ///
+    /// # Arguments
+    /// * `config_path` - The path to the xorgxrdp Xorg configuration.
+    ///
+    /// # Returns
+    /// A new `XorgXrdp` instance.
+    pub fn new(config_path: &str) -> Self {
+        Self {
+            config_path: config_path.into(),
+        }
+    }
+}
+
+impl DisplayServer for XorgXrdp {
+    fn kind(&self) -> DisplayServerKind {
+        DisplayServerKind::Xorgxrdp
+    }
+
+    fn program(&self) -> &str {
+        "Xorg"
+    }
+
+    fn arguments(&self, display: &str, authority_file_path: &str, _runtime_path: &str, _resolution: &ScreenResolution, properties: &DisplayProperties) -> Vec<String> {
+        vec![
+            display.into(),
+            "-auth".into(),
+            authority_file_path.into(),
+            "-config".into(),
+            self.config_path.clone(),
+            "-depth".into(),
+            properties.depth().to_string(),
+            "-dpi".into(),
+            properties.dpi().to_string(),
+            "-verbose".into(),
+        ]
+    }
+
+    fn environment(&self, resolution: &ScreenResolution) -> Vec<(String, String)> {
+        let (screen_width, screen_height) = resolution.split();
+        vec![
+            ("XORG_RUN_AS_USER_OK".into(), "1".into()),
+            ("XRDP_START_WIDTH".into(), screen_width.to_string()),
+            ("XRDP_START_HEIGHT".into(), screen_height.to_string()),
+        ]
+    }
+
+    fn resize(&self, display: &str, authority_file_path: &str, resolution: &ScreenResolution) -> Result<(), ApplicationError> {
+        set_screen_size(display, authority_file_path, resolution)
+    }
+}
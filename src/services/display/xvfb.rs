@@ -0,0 +1,49 @@
+use crate::common::{DisplayProperties, DisplayServerKind, ScreenResolution};
+
+use super::DisplayServer;
+
+/// The `Xvfb` struct launches the Xvfb virtual framebuffer server, whose screen is created with the
+/// requested resolution. It needs no video driver, which makes it suitable for containers and CI.
+pub struct Xvfb;
+
+impl Xvfb {
+    /// Creates a new `Xvfb` instance.
+    ///
+    /// # Returns
+    /// A new `Xvfb` instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for Xvfb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DisplayServer for Xvfb {
+    fn kind(&self) -> DisplayServerKind {
+        DisplayServerKind::Xvfb
+    }
+
+    fn program(&self) -> &str {
+        "Xvfb"
+    }
+
+    fn arguments(&self, display: &str, authority_file_path: &str, _runtime_path: &str, resolution: &ScreenResolution, properties: &DisplayProperties) -> Vec<String> {
+        let (screen_width, screen_height) = resolution.split();
+        vec![
+            display.into(),
+            "-auth".into(),
+            authority_file_path.into(),
+            "-screen".into(),
+            "0".into(),
+            format!("{}x{}x{}", screen_width, screen_height, properties.depth()),
+            "-dpi".into(),
+            properties.dpi().to_string(),
+            "-nolisten".into(),
+            "tcp".into(),
+        ]
+    }
+}
@@ -0,0 +1,59 @@
+use crate::common::{ApplicationError, DisplayProperties, DisplayServerKind, ScreenResolution};
+
+use super::{set_screen_size, DisplayServer};
+
+/// The `Xvnc` struct launches the TigerVNC Xvnc server with the requested geometry. The VNC server only
+/// listens on a unix socket in the runtime directory of the session user, not on a TCP port.
+pub struct Xvnc;
+
+impl Xvnc {
+    /// Creates a new `Xvnc` instance.
+    ///
+    /// # Returns
+    /// A new `Xvnc` instance.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for Xvnc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DisplayServer for Xvnc {
+    fn kind(&self) -> DisplayServerKind {
+        DisplayServerKind::Xvnc
+    }
+
+    fn program(&self) -> &str {
+        "Xvnc"
+    }
+
+    fn arguments(&self, display: &str, authority_file_path: &str, runtime_path: &str, resolution: &ScreenResolution, properties: &DisplayProperties) -> Vec<String> {
+        vec![
+            display.into(),
+            "-auth".into(),
+            authority_file_path.into(),
+            "-geometry".into(),
+            resolution.to_string(),
+            "-depth".into(),
+            properties.depth().to_string(),
+            "-dpi".into(),
+            properties.dpi().to_string(),
+            "-rfbport".into(),
+            "-1".into(),
+            "-rfbunixpath".into(),
+            format!("{}/vnc.sock", runtime_path),
+            "-SecurityTypes".into(),
+            "None".into(),
+            "-nolisten".into(),
+            "tcp".into(),
+        ]
+    }
+
+    fn resize(&self, display: &str, authority_file_path: &str, resolution: &ScreenResolution) -> Result<(), ApplicationError> {
+        set_screen_size(display, authority_file_path, resolution)
+    }
+}
@@ -0,0 +1,230 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::common::{ApplicationError, DrainSettings};
+use crate::fs::{chmod, mkdir};
+
+/// The `DrainState` struct represents the drain mode of the session manager: new logins are rejected
+/// (except, optionally, those returning the existing session of a user) and the remaining sessions are
+/// terminated at the deadline, if any.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DrainState {
+    allow_existing: bool,
+    deadline: Option<i64>,
+    started: i64,
+    #[serde(default)]
+    warned: bool,
+}
+
+impl DrainState {
+    /// Returns whether the users that already have a session can still log in to it.
+    pub fn allow_existing(&self) -> bool {
+        self.allow_existing
+    }
+
+    /// Returns the unix timestamp at which the remaining sessions are terminated, if any.
+    pub fn deadline(&self) -> Option<i64> {
+        self.deadline
+    }
+
+    /// Returns the unix timestamp at which the drain mode was enabled.
+    pub fn started(&self) -> i64 {
+        self.started
+    }
+
+    /// Returns whether the users have been warned of the deadline.
+    pub fn is_warned(&self) -> bool {
+        self.warned
+    }
+
+    /// Determines whether the deadline has been reached.
+    ///
+    /// # Arguments
+    /// * `now` - The current unix timestamp.
+    ///
+    /// # Returns
+    /// `true` if there is a deadline and it has been reached, otherwise `false`.
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.deadline.is_some_and(|deadline| now >= deadline)
+    }
+}
+
+/// The `DrainService` struct manages the drain mode of the session manager, persisted in a file only
+/// readable by root so that it survives restarts.
+pub struct DrainService {
+    path: String,
+    warning: u64,
+    state: Mutex<Option<DrainState>>,
+}
+
+impl DrainService {
+    /// Creates a new `DrainService` instance, restoring the drain mode persisted before a restart.
+    ///
+    /// # Arguments
+    /// * `settings` - The drain mode settings.
+    ///
+    /// # Returns
+    /// A new `DrainService` instance.
+    pub fn new(settings: &DrainSettings) -> Self {
+        let path = settings.state_path().to_string();
+        let state = match Self::load(&path) {
+            Ok(state) => state,
+            Err(error) => {
+                error!("{}", error);
+                None
+            }
+        };
+        if state.is_some() {
+            warn!("The session manager is draining: new logins are rejected");
+        }
+
+        Self {
+            path,
+            warning: settings.warning(),
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Returns the number of minutes before the deadline at which the users are warned.
+    pub fn warning(&self) -> u64 {
+        self.warning
+    }
+
+    /// Returns the drain mode, if the session manager is draining.
+    pub fn state(&self) -> Option<DrainState> {
+        self.state.lock().ok().and_then(|state| state.clone())
+    }
+
+    /// Enables the drain mode, replacing the deadline if the session manager is already draining.
+    ///
+    /// # Arguments
+    /// * `allow_existing` - Whether the users that already have a session can still log in to it.
+    /// * `deadline` - The unix timestamp at which the remaining sessions are terminated, if any.
+    ///
+    /// # Returns
+    /// A `Result` containing the `DrainState` or an `ApplicationError`.
+    pub fn start(&self, allow_existing: bool, deadline: Option<i64>) -> Result<DrainState, ApplicationError> {
+        let now = chrono::Utc::now().timestamp();
+        if deadline.is_some_and(|deadline| deadline <= now) {
+            return Err(ApplicationError::session("The drain deadline must be in the future"));
+        }
+
+        let mut state = self.state.lock().map_err(|_| ApplicationError::session("Could not access the drain mode"))?;
+        let drain_state = DrainState {
+            allow_existing,
+            deadline,
+            started: state.as_ref().map_or(now, |state| state.started),
+            warned: false,
+        };
+        self.save(&drain_state)?;
+        *state = Some(drain_state.clone());
+        Ok(drain_state)
+    }
+
+    /// Disables the drain mode.
+    ///
+    /// # Returns
+    /// A `Result` indicating whether the session manager was draining, or an `ApplicationError`.
+    pub fn stop(&self) -> Result<bool, ApplicationError> {
+        let mut state = self.state.lock().map_err(|_| ApplicationError::session("Could not access the drain mode"))?;
+        if Path::new(&self.path).exists() {
+            fs::remove_file(&self.path)
+                .map_err(|error| ApplicationError::environment(format!("Could not remove the drain state {}: {}", self.path, error)))?;
+        }
+        Ok(state.take().is_some())
+    }
+
+    /// Verifies that a user may log in while the session manager is draining.
+    ///
+    /// # Arguments
+    /// * `has_session` - Whether the user already has a session.
+    ///
+    /// # Returns
+    /// A `Result` indicating the login is permitted or a draining `ApplicationError`.
+    pub fn check_login(&self, has_session: bool) -> Result<(), ApplicationError> {
+        match self.state() {
+            Some(state) if state.allow_existing && has_session && !state.is_expired(chrono::Utc::now().timestamp()) => Ok(()),
+            Some(state) if state.allow_existing => Err(ApplicationError::draining("Only existing sessions can be logged in to while the host is being drained")),
+            Some(_) => Err(ApplicationError::draining("New logins are rejected while the host is being drained")),
+            None => Ok(()),
+        }
+    }
+
+    /// Records that the users have been warned of the deadline, so that they are only warned once.
+    ///
+    /// # Returns
+    /// `true` if the users had not been warned yet, otherwise `false`.
+    pub fn set_warned(&self) -> bool {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(_) => return false,
+        };
+        match state.as_mut() {
+            Some(drain_state) if !drain_state.warned => {
+                drain_state.warned = true;
+                if let Err(error) = self.save(drain_state) {
+                    error!("{}", error);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Loads the persisted drain mode, if any.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the drain state file.
+    ///
+    /// # Returns
+    /// A `Result` containing the `DrainState` if the session manager is draining, or an `ApplicationError`.
+    fn load(path: &str) -> Result<Option<DrainState>, ApplicationError> {
+        if !Path::new(path).exists() {
+            return Ok(None);
+        }
+        let json = fs::read_to_string(path)
+            .map_err(|error| ApplicationError::environment(format!("Could not read the drain state {}: {}", path, error)))?;
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|error| ApplicationError::environment(format!("Could not parse the drain state {}: {}", path, error)))
+    }
+
+    /// Saves the drain mode atomically, ensuring the file is only accessible by root.
+    ///
+    /// # Arguments
+    /// * `state` - The drain mode.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    fn save(&self, state: &DrainState) -> Result<(), ApplicationError> {
+        if let Some(directory) = Path::new(&self.path).parent().and_then(|directory| directory.to_str()) {
+            if !directory.is_empty() && !Path::new(directory).exists() {
+                mkdir(directory)?;
+                chmod(directory, 0o700)?;
+            }
+        }
+
+        let json = serde_json::to_string_pretty(state)
+            .map_err(|error| ApplicationError::environment(format!("Could not serialize the drain state: {}", error)))?;
+        let temporary_path = format!("{}.tmp", self.path);
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&temporary_path)
+            .map_err(|error| ApplicationError::environment(format!("Could not write the drain state {}: {}", temporary_path, error)))?;
+        file.write_all(json.as_bytes())
+            .and_then(|_| file.sync_all())
+            .map_err(|error| ApplicationError::environment(format!("Could not write the drain state {}: {}", temporary_path, error)))?;
+        chmod(&temporary_path, 0o600)?;
+
+        fs::rename(&temporary_path, &self.path)
+            .map_err(|error| ApplicationError::environment(format!("Could not replace the drain state {}: {}", self.path, error)))
+    }
+}
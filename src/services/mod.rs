@@ -1,8 +1,12 @@
 pub use client::Client;
+pub use display::DisplayServer;
+pub use drain::{DrainService, DrainState};
 pub use server::Server;
 pub use session::SessionService;
 pub use xorg::XorgService;
 
+mod display;
+mod drain;
 mod server;
 mod session;
 mod xorg;
@@ -2,18 +2,22 @@ use std::fs;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 use nix::unistd::User;
 use uuid::Uuid;
 
 use crate::{
-    authentication::{Authenticator, Credentials},
-    common::{ApplicationError, Encoder, Request, Response, ScreenResolution, Settings},
+    authentication::{AccessControl, Authenticator, Credentials, LoginThrottle, ReconnectionTokenStore, TicketVerifier, TotpStore},
+    common::{ApplicationError, ApplicationErrorKind, Encoder, Notification, Request, Response, ScreenResolution, Session, SessionFilter, SessionOptions, Settings},
 };
 use crate::common::Account;
-use crate::dto::SessionDto;
-use crate::fs::chown;
+use crate::dto::{DesktopDto, DrainDto, GrantDto, LockoutDto, LogoutDto, NotificationDto, SessionDto};
+use crate::fs::{chmod, chown};
 
-use super::{SessionService, XorgService};
+use super::{DrainService, SessionService, XorgService};
+
+/// The interval between two evaluations of the session policies (such as the login windows).
+const POLICY_ENFORCEMENT_INTERVAL: Duration = Duration::from_secs(30);
 
 /// The `Server` struct represents the WebX Session Manager server, which handles
 /// requests for user authentication, session creation, and session management.
@@ -22,6 +26,7 @@ pub struct Server {
     session_service: SessionService,
     encoder: Encoder,
     ipc: String,
+    admin_ipc: Option<String>,
 }
 
 impl Server {
@@ -35,15 +40,24 @@ impl Server {
     /// A new `Server` instance.
     pub fn new(settings: Settings, context: zmq::Context) -> Self {
         let authenticator = Authenticator::new(settings.authentication().service().to_owned());
-        let xorg_service = XorgService::new(settings.xorg().to_owned());
-        let session_service = SessionService::new(authenticator, xorg_service);
+        let xorg_service = XorgService::new(settings.xorg().to_owned(), settings.profiles().to_vec(), settings.default_profile().map(String::from));
+        let ticket_verifier = settings.authentication().tickets().as_ref().map(TicketVerifier::new);
+        let reconnection_tokens = settings.authentication().reconnection().as_ref().map(ReconnectionTokenStore::new);
+        let login_throttle = settings.authentication().rate_limit().as_ref().map(LoginThrottle::new);
+        let access_control = settings.access().to_owned().map(AccessControl::new);
+        let totp_store = settings.authentication().totp().as_ref().map(TotpStore::new);
+        let revalidation = settings.revalidation().to_owned();
+        let drain_service = DrainService::new(&settings.drain());
+        let session_service = SessionService::new(authenticator, xorg_service, ticket_verifier, reconnection_tokens, login_throttle, access_control, totp_store, revalidation, drain_service);
         let ipc = settings.transport().ipc().to_owned();
+        let admin_ipc = settings.transport().admin_ipc().to_owned();
         let encoder = Encoder::new();
         Self {
             context,
             session_service,
             encoder,
-            ipc
+            ipc,
+            admin_ipc
         }
     }
 
@@ -56,8 +70,16 @@ impl Server {
     /// A `Result` indicating success or an `ApplicationError`.
     pub fn run(&mut self, stop_signal: Arc<AtomicBool>) -> Result<(), ApplicationError> {
         let rep_socket = self.create_rep_socket()?;
+        let admin_socket = self.create_admin_socket()?;
+
+        let mut items = vec![rep_socket.as_poll_item(zmq::POLLIN)];
+        if let Some(admin_socket) = &admin_socket {
+            items.push(admin_socket.as_poll_item(zmq::POLLIN));
+        }
 
-        let mut items = [rep_socket.as_poll_item(zmq::POLLIN)];
+        let mut last_policy_enforcement = Instant::now();
+        let mut last_revalidation = Instant::now();
+        let revalidation_interval = self.session_service.revalidation_interval().map(Duration::from_secs);
 
         // listen for messages until a kill signal is received
         while !stop_signal.load(Ordering::SeqCst) {
@@ -67,9 +89,30 @@ impl Server {
                 // clean up zombie x session
                 self.session_service.clean_up();
 
+                // periodically terminate sessions that are no longer permitted
+                if last_policy_enforcement.elapsed() >= POLICY_ENFORCEMENT_INTERVAL {
+                    self.session_service.enforce_login_windows();
+                    self.session_service.enforce_max_durations();
+                    self.session_service.enforce_drain();
+                    last_policy_enforcement = Instant::now();
+                }
+
+                // periodically revalidate the users of the sessions
+                if revalidation_interval.is_some_and(|interval| last_revalidation.elapsed() >= interval) {
+                    self.session_service.revalidate_sessions();
+                    last_revalidation = Instant::now();
+                }
+
                 // Check for REQ-REP message (if running)
                 if items[0].is_readable() {
-                    self.handle_request(&rep_socket);
+                    self.handle_request(&rep_socket, false);
+                }
+
+                // Check for admin REQ-REP message (if configured)
+                if let Some(admin_socket) = &admin_socket {
+                    if items[1].is_readable() {
+                        self.handle_request(admin_socket, true);
+                    }
                 }
             }
         }
@@ -99,6 +142,10 @@ impl Server {
     fn clean_up(&self) -> Result<(), ApplicationError> {
         debug!("Deleting ipc socket descriptor");
         fs::remove_file(&self.ipc)?;
+        if let Some(admin_ipc) = &self.admin_ipc {
+            debug!("Deleting admin ipc socket descriptor");
+            fs::remove_file(admin_ipc)?;
+        }
         // killing all sessions
         debug!("Killing all sessions...");
         self.session_service.kill_all()?;
@@ -129,11 +176,37 @@ impl Server {
         Ok(socket)
     }
 
-    /// Handles incoming requests from the reply socket.
+    /// Creates the ZeroMQ reply socket for handling admin requests, if an admin IPC path is configured.
+    /// The socket is only accessible by root.
+    ///
+    /// # Returns
+    /// A `Result` containing the optional `zmq::Socket` or an `ApplicationError`.
+    fn create_admin_socket(&self) -> Result<Option<zmq::Socket>, ApplicationError> {
+        if let Some(admin_ipc) = &self.admin_ipc {
+            let address = format!("ipc://{}", admin_ipc);
+            let socket = self.context.socket(zmq::REP)?;
+            socket.set_linger(0)?;
+
+            if let Err(error) = socket.bind(&address) {
+                return Err(ApplicationError::transport(format!("Failed to bind admin reply socket to {}: {}", &address, error)));
+            }
+            info!("Server bound to admin address {} and listening for requests", &address);
+
+            debug!("Restricting access of admin ipc address to root");
+            chown(admin_ipc, 0, 0)?;
+            chmod(admin_ipc, 0o600)?;
+
+            return Ok(Some(socket));
+        }
+        Ok(None)
+    }
+
+    /// Handles incoming requests from a reply socket.
     ///
     /// # Arguments
     /// * `rep_socket` - The ZeroMQ reply socket.
-    fn handle_request(&self, rep_socket: &zmq::Socket) {
+    /// * `is_admin` - Whether the request was received on the admin socket.
+    fn handle_request(&self, rep_socket: &zmq::Socket, is_admin: bool) {
         let mut message = zmq::Message::new();
 
         if let Err(error) = rep_socket.recv(&mut message, 0) {
@@ -141,15 +214,68 @@ impl Server {
         } else if let Some(request) = message.as_str() {
             info!("Received a request");
             match self.encoder.decode(request) {
+                Some(request) if request.is_admin() && !is_admin => self.handle_forbidden_request(rep_socket),
                 Some(request) => match request {
-                    Request::Login { username, password, width, height } => {
+                    Request::Login { username, password, width, height, otp, display_server, desktop, profile, monitors, dpi, depth, keyboard, locale, timezone, environment, labels } => {
                         debug!("Handling login request");
-                        let credentials = Credentials::new(username, password);
-                        let resolution = ScreenResolution::new(width, height);
-                        self.handle_login_request(rep_socket, credentials, resolution)
+                        let mut credentials = Credentials::new(username, password);
+                        if let Some(otp) = otp {
+                            credentials = credentials.with_otp(otp);
+                        }
+                        let resolution = ScreenResolution::new(width, height).with_monitors(monitors.unwrap_or_default());
+                        let options = SessionOptions::default()
+                            .with_display_server(display_server)
+                            .with_desktop(desktop)
+                            .with_profile(profile)
+                            .with_dpi(dpi)
+                            .with_depth(depth)
+                            .with_keyboard(keyboard)
+                            .with_locale(locale)
+                            .with_timezone(timezone)
+                            .with_environment(environment.unwrap_or_default())
+                            .with_labels(labels.unwrap_or_default());
+                        self.handle_login_request(rep_socket, credentials, resolution, options)
                     }
-                    Request::Who => self.handle_who_request(rep_socket),
+                    Request::LoginTicket { ticket, width, height, display_server, desktop, profile, monitors, dpi, depth, keyboard, locale, timezone, environment, labels } => {
+                        debug!("Handling login ticket request");
+                        let resolution = ScreenResolution::new(width, height).with_monitors(monitors.unwrap_or_default());
+                        let options = SessionOptions::default()
+                            .with_display_server(display_server)
+                            .with_desktop(desktop)
+                            .with_profile(profile)
+                            .with_dpi(dpi)
+                            .with_depth(depth)
+                            .with_keyboard(keyboard)
+                            .with_locale(locale)
+                            .with_timezone(timezone)
+                            .with_environment(environment.unwrap_or_default())
+                            .with_labels(labels.unwrap_or_default());
+                        self.handle_login_ticket_request(rep_socket, &ticket, resolution, options)
+                    }
+                    Request::Desktops => self.handle_desktops_request(rep_socket),
+                    Request::Reconnect { token } => self.handle_reconnect_request(rep_socket, &token),
+                    Request::Who(filter) => self.handle_who_request(rep_socket, filter.unwrap_or_default(), is_admin),
+                    Request::Get { id } => self.handle_get_request(rep_socket, id, is_admin),
                     Request::Logout { id } => self.handle_logout_request(rep_socket, id),
+                    Request::LogoutSessions { filter, all } => self.handle_logout_sessions_request(rep_socket, filter, all),
+                    Request::Resize { id, width, height, monitors } => {
+                        let resolution = ScreenResolution::new(width, height).with_monitors(monitors.unwrap_or_default());
+                        self.handle_resize_request(rep_socket, id, resolution)
+                    }
+                    Request::Grant { id, grantee, view_only, cookie } => self.handle_grant_request(rep_socket, id, &grantee, view_only, cookie, is_admin),
+                    Request::RevokeGrant { id, grantee, cookie } => self.handle_revoke_grant_request(rep_socket, id, &grantee, cookie, is_admin),
+                    Request::Lockouts => self.handle_lockouts_request(rep_socket),
+                    Request::ClearLockouts { username } => self.handle_clear_lockouts_request(rep_socket, username),
+                    Request::TotpEnroll { username } => self.handle_totp_enroll_request(rep_socket, &username),
+                    Request::TotpReset { username } => self.handle_totp_reset_request(rep_socket, &username),
+                    Request::RotateCookie { id } => self.handle_rotate_cookie_request(rep_socket, id),
+                    Request::Drain { allow_existing, deadline } => self.handle_drain_request(rep_socket, allow_existing, deadline),
+                    Request::Undrain => self.handle_undrain_request(rep_socket),
+                    Request::DrainStatus => self.handle_drain_status_request(rep_socket),
+                    Request::Notify { ids, filter, all, summary, body, urgency } => {
+                        let notification = Notification::new(summary, body, urgency);
+                        self.handle_notify_request(rep_socket, ids, filter, all, notification)
+                    }
 
                 },
                 None => self.handle_unknown_request(rep_socket),
@@ -169,21 +295,48 @@ impl Server {
         }
     }
 
+    /// Handles admin requests received on the standard socket by sending an error response.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    fn handle_forbidden_request(&self, rep_socket: &zmq::Socket) {
+        warn!("Rejected an admin request received on the standard socket");
+        let response = Response::Error { message: "Admin requests are only accepted on the admin socket".into() };
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
     /// Handles login requests by authenticating the user and creating a session.
     ///
     /// # Arguments
     /// * `rep_socket` - The ZeroMQ reply socket.
     /// * `credentials` - The user's credentials.
     /// * `resolution` - The screen resolution for the session.
+    /// * `options` - The options chosen at login, such as the display server and the desktop.
     fn handle_login_request(&self,
                             rep_socket: &zmq::Socket,
                             credentials: Credentials,
                             resolution: ScreenResolution,
+                            options: SessionOptions,
     ) {
         debug!("Creating session for user {} with resolution: {}", credentials.username(), resolution);
-        let response = match self.session_service.create_session(&credentials, resolution) {
+        let response = match self.session_service.create_session(&credentials, resolution, options) {
             Ok(session) => {
-                Response::Login(SessionDto::from(&session))
+                Response::Login(self.create_session_dto(&session))
+            },
+            Err(error) if error.kind() == ApplicationErrorKind::LockedOut => {
+                warn!("{}", error);
+                Response::LockedOut { message: format!("{}", error) }
+            },
+            Err(error) if error.kind() == ApplicationErrorKind::Draining => {
+                warn!("{}", error);
+                Response::Draining { message: error.message().into() }
+            },
+            Err(error) if error.kind() == ApplicationErrorKind::InvalidResolution => {
+                warn!("{}", error);
+                Response::InvalidResolution { message: error.message().into() }
             },
             Err(error) => {
                 error!("{}", error);
@@ -196,15 +349,143 @@ impl Server {
         }
     }
 
+    /// Handles login requests of pre-authenticated users by verifying their login ticket and creating a session.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `ticket` - The signed login ticket.
+    /// * `resolution` - The screen resolution for the session.
+    /// * `options` - The options chosen at login, such as the display server and the desktop.
+    fn handle_login_ticket_request(&self,
+                                   rep_socket: &zmq::Socket,
+                                   ticket: &str,
+                                   resolution: ScreenResolution,
+                                   options: SessionOptions,
+    ) {
+        debug!("Creating session from login ticket with resolution: {}", resolution);
+        let response = match self.session_service.create_session_with_ticket(ticket, resolution, options) {
+            Ok(session) => {
+                Response::Login(self.create_session_dto(&session))
+            },
+            Err(error) if error.kind() == ApplicationErrorKind::Draining => {
+                warn!("{}", error);
+                Response::Draining { message: error.message().into() }
+            },
+            Err(error) if error.kind() == ApplicationErrorKind::InvalidResolution => {
+                warn!("{}", error);
+                Response::InvalidResolution { message: error.message().into() }
+            },
+            Err(error) => {
+                error!("{}", error);
+                Response::Error { message: format!("Error creating session: {}", error) }
+            }
+        };
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Handles reconnection requests by validating the reconnection token and returning the existing session.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `token` - The reconnection token.
+    fn handle_reconnect_request(&self, rep_socket: &zmq::Socket, token: &str) {
+        debug!("Reconnecting to session with reconnection token");
+        let response = match self.session_service.reconnect(token) {
+            Ok(session) => {
+                info!("Reconnected to session {} of user {}", session.id(), session.username());
+                Response::Reconnect(self.create_session_dto(&session))
+            },
+            Err(error) if error.kind() == ApplicationErrorKind::Draining => {
+                warn!("{}", error);
+                Response::Draining { message: error.message().into() }
+            },
+            Err(error) => {
+                error!("{}", error);
+                Response::Error { message: format!("Error reconnecting to session: {}", error) }
+            }
+        };
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Creates the `SessionDto` returned to the client that logged in, including the cookie of the session and a new
+    /// reconnection token if enabled.
+    ///
+    /// # Arguments
+    /// * `session` - The session.
+    ///
+    /// # Returns
+    /// The `SessionDto` for the session.
+    fn create_session_dto(&self, session: &Session) -> SessionDto {
+        let session_dto = SessionDto::from(session).with_cookie(session.cookie().into());
+        match self.session_service.issue_reconnection_token(session) {
+            Some(token) => session_dto.with_reconnection_token(token),
+            None => session_dto,
+        }
+    }
     
-    /// Handles requests to list all active sessions.
+    /// Creates the `SessionDto` of a session listed by a who or get request, including the reason it has been flagged.
+    /// The cookies of the session and of its grants are only included for admin requests.
+    ///
+    /// # Arguments
+    /// * `session` - The session.
+    /// * `is_admin` - Whether the request was received on the admin socket.
+    ///
+    /// # Returns
+    /// The `SessionDto` for the session.
+    fn create_listed_session_dto(&self, session: &Session, is_admin: bool) -> SessionDto {
+        let session_dto = if is_admin {
+            let grants = session.grants()
+                .iter()
+                .map(|grant| GrantDto::from(grant).with_cookie(grant.cookie().into()))
+                .collect();
+            SessionDto::from(session).with_cookie(session.cookie().into()).with_grants(grants)
+        } else {
+            SessionDto::from(session)
+        };
+        match self.session_service.revalidation_failure(session.id()) {
+            Some(reason) => session_dto.with_revalidation_failure(reason),
+            None => session_dto,
+        }
+    }
+
+    /// Retrieves the active sessions matching a filter, in the order of the filter.
+    ///
+    /// # Arguments
+    /// * `filter` - The criteria of the sessions and their order.
+    ///
+    /// # Returns
+    /// A vector of the matching `Session` instances.
+    fn get_filtered_sessions(&self, filter: &SessionFilter) -> Vec<Session> {
+        let mut sessions: Vec<Session> = self.session_service
+            .get_all()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|session| filter.matches(session, self.session_service.get_state(session.id())))
+            .collect();
+        filter.sort(&mut sessions);
+        sessions
+    }
+
+    /// Handles requests to list the active sessions. The cookies of the sessions are only listed for admin requests.
     ///
     /// # Arguments
     /// * `rep_socket` - The ZeroMQ reply socket.
-    fn handle_who_request(&self, rep_socket: &zmq::Socket) {
+    /// * `filter` - The criteria of the sessions to list and their order.
+    /// * `is_admin` - Whether the request was received on the admin socket.
+    fn handle_who_request(&self, rep_socket: &zmq::Socket, filter: SessionFilter, is_admin: bool) {
         debug!("Listing sessions");
-        let sessions = self.session_service.get_all().unwrap_or_default();
-        let dtos = sessions.iter().map(|session| session.into()).collect();
+        let sessions = self.get_filtered_sessions(&filter);
+
+        let dtos = sessions
+            .iter()
+            .map(|session| self.create_listed_session_dto(session, is_admin))
+            .collect();
         let response = Response::Who(dtos);
         let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
         if let Err(error) = rep_socket.send(&json[..], 0) {
@@ -212,6 +493,30 @@ impl Server {
         }
     }
 
+    /// Handles requests to retrieve a single session. The cookies of the session are only included for admin requests.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `id` - The session ID.
+    /// * `is_admin` - Whether the request was received on the admin socket.
+    fn handle_get_request(&self, rep_socket: &zmq::Socket, id: String, is_admin: bool) {
+        let response = match Uuid::from_str(&id) {
+            Ok(id) => match self.session_service.get_by_id(&id) {
+                Some(session) => Response::Get(self.create_listed_session_dto(&session, is_admin)),
+                None => Response::Error { message: format!("Session {} not found", id) },
+            },
+            Err(_) => {
+                error!("Invalid session id {} provided", id);
+                Response::Error { message: format!("Invalid session id {} provided", id) }
+            }
+        };
+
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
     /// Handles logout requests by terminating the specified session.
     ///
     /// # Arguments
@@ -239,4 +544,392 @@ impl Server {
 
     }
 
+    /// Handles bulk logout requests by terminating all the sessions matching a filter. A session that cannot be
+    /// logged out does not prevent the others from being logged out: the result of each session is returned.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `filter` - The criteria of the sessions to terminate.
+    /// * `all` - Whether all the sessions are terminated when the filter has no criteria.
+    fn handle_logout_sessions_request(&self, rep_socket: &zmq::Socket, filter: SessionFilter, all: bool) {
+        let response = if filter.is_empty() && !all {
+            warn!("Rejected a bulk logout request without criteria");
+            Response::Error { message: "A filter is required to log out sessions, or all to log out all the sessions".into() }
+        } else {
+            let results: Vec<LogoutDto> = self.get_filtered_sessions(&filter)
+                .iter()
+                .map(|session| {
+                    let error = match self.session_service.kill_by_id(*session.id()) {
+                        Ok(_) => None,
+                        Err(error) => {
+                            error!("Could not logout session {}: {}", session.id(), error);
+                            Some(format!("{}", error))
+                        }
+                    };
+                    LogoutDto::new(session.id().to_string(), session.username().into(), error)
+                })
+                .collect();
+            audit!("Bulk logout of {} sessions", results.iter().filter(|result| result.is_logged_out()).count());
+            Response::LogoutSessions(results)
+        };
+
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Handles resize requests by changing the screen resolution of the specified session.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `id` - The session ID to resize.
+    /// * `resolution` - The new screen resolution.
+    fn handle_resize_request(&self, rep_socket: &zmq::Socket, id: String, resolution: ScreenResolution) {
+        let response = match Uuid::from_str(&id) {
+            Ok(id) => match self.session_service.resize(id, resolution) {
+                Ok(session) => Response::Resize(SessionDto::from(&session)),
+                Err(error) if error.kind() == ApplicationErrorKind::InvalidResolution => {
+                    warn!("{}", error);
+                    Response::InvalidResolution { message: error.message().into() }
+                },
+                Err(error) => {
+                    error!("Could not resize session: {}", error);
+                    Response::Error { message: format!("{}", error)}
+                }
+            },
+            Err(_) => {
+                error!("Invalid session id {} provided", id);
+                Response::Error { message: format!("Invalid session id {} provided", id) }
+            }
+        };
+
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Determines whether a request concerning a session is made for its owner: admin requests are always permitted,
+    /// other requests must provide the cookie of the session.
+    ///
+    /// # Arguments
+    /// * `id` - The session ID.
+    /// * `cookie` - The cookie provided with the request, if any.
+    /// * `is_admin` - Whether the request was received on the admin socket.
+    ///
+    /// # Returns
+    /// `true` if the request is permitted, otherwise `false`.
+    fn is_session_owner(&self, id: &Uuid, cookie: Option<&str>, is_admin: bool) -> bool {
+        is_admin || cookie.is_some_and(|cookie| self.session_service.is_session_cookie(id, cookie))
+    }
+
+    /// Handles requests to grant another user access to a session.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `id` - The session ID.
+    /// * `grantee` - The username of the user granted access.
+    /// * `view_only` - Whether the grantee may only view the session.
+    /// * `cookie` - The cookie of the session, required for requests that are not received on the admin socket.
+    /// * `is_admin` - Whether the request was received on the admin socket.
+    fn handle_grant_request(&self, rep_socket: &zmq::Socket, id: String, grantee: &str, view_only: bool, cookie: Option<String>, is_admin: bool) {
+        let response = match Uuid::from_str(&id) {
+            Ok(id) if !self.is_session_owner(&id, cookie.as_deref(), is_admin) => {
+                audit!("Rejected the request to grant user {} access to session {}: invalid session cookie", grantee, id);
+                Response::Error { message: "The cookie of the session is required to grant access".into() }
+            }
+            Ok(id) => match self.session_service.grant(id, grantee, view_only) {
+                Ok(grant) => Response::Grant(GrantDto::from(&grant).with_cookie(grant.cookie().into())),
+                Err(error) => {
+                    error!("Could not grant user {} access to session {}: {}", grantee, id, error);
+                    Response::Error { message: format!("{}", error) }
+                }
+            },
+            Err(_) => {
+                error!("Invalid session id {} provided", id);
+                Response::Error { message: format!("Invalid session id {} provided", id) }
+            }
+        };
+
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Handles requests to revoke the access to a session granted to a user.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `id` - The session ID.
+    /// * `grantee` - The username of the user granted access.
+    /// * `cookie` - The cookie of the session, required for requests that are not received on the admin socket.
+    /// * `is_admin` - Whether the request was received on the admin socket.
+    fn handle_revoke_grant_request(&self, rep_socket: &zmq::Socket, id: String, grantee: &str, cookie: Option<String>, is_admin: bool) {
+        let response = match Uuid::from_str(&id) {
+            Ok(id) if !self.is_session_owner(&id, cookie.as_deref(), is_admin) => {
+                audit!("Rejected the request to revoke the access of user {} to session {}: invalid session cookie", grantee, id);
+                Response::Error { message: "The cookie of the session is required to revoke access".into() }
+            }
+            Ok(id) => match self.session_service.revoke_grant(id, grantee) {
+                Ok(revoked) => Response::RevokeGrant { revoked },
+                Err(error) => {
+                    error!("Could not revoke the access of user {} to session {}: {}", grantee, id, error);
+                    Response::Error { message: format!("{}", error) }
+                }
+            },
+            Err(_) => {
+                error!("Invalid session id {} provided", id);
+                Response::Error { message: format!("Invalid session id {} provided", id) }
+            }
+        };
+
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Handles requests to list the desktop environments offered to the users.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    fn handle_desktops_request(&self, rep_socket: &zmq::Socket) {
+        debug!("Listing desktops");
+        let desktops = self.session_service.get_desktops();
+        let dtos = desktops.iter().map(DesktopDto::from).collect();
+        let response = Response::Desktops(dtos);
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Handles admin requests to list the usernames that are locked out.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    fn handle_lockouts_request(&self, rep_socket: &zmq::Socket) {
+        debug!("Listing lockouts");
+        let lockouts = self.session_service.get_lockouts();
+        let dtos = lockouts.iter().map(LockoutDto::from).collect();
+        let response = Response::Lockouts(dtos);
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Handles admin requests to clear the lockout of a username, or of all usernames.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `username` - The username to clear, or `None` to clear all usernames.
+    fn handle_clear_lockouts_request(&self, rep_socket: &zmq::Socket, username: Option<String>) {
+        debug!("Clearing lockouts");
+        let cleared = self.session_service.clear_lockouts(username.as_deref());
+        let response = Response::ClearLockouts { cleared };
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Handles admin requests to enroll a user in the TOTP second factor.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `username` - The username of the user to enroll.
+    fn handle_totp_enroll_request(&self, rep_socket: &zmq::Socket, username: &str) {
+        debug!("Enrolling user {} for TOTP", username);
+        let response = match self.session_service.enroll_totp(username) {
+            Ok(uri) => Response::TotpEnroll { uri },
+            Err(error) => {
+                error!("Failed to enroll user {} for TOTP: {}", username, error);
+                Response::Error { message: format!("{}", error) }
+            }
+        };
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Handles admin requests to remove the TOTP enrollment of a user.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `username` - The username of the user.
+    fn handle_totp_reset_request(&self, rep_socket: &zmq::Socket, username: &str) {
+        debug!("Resetting TOTP enrollment of user {}", username);
+        let response = match self.session_service.reset_totp(username) {
+            Ok(reset) => Response::TotpReset { reset },
+            Err(error) => {
+                error!("Failed to reset TOTP enrollment of user {}: {}", username, error);
+                Response::Error { message: format!("{}", error) }
+            }
+        };
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Handles admin requests to replace the cookie given to the clients of a session.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `id` - The ID of the session.
+    fn handle_rotate_cookie_request(&self, rep_socket: &zmq::Socket, id: String) {
+        debug!("Rotating the cookie of session {}", id);
+        let response = match Uuid::from_str(&id) {
+            Ok(id) => match self.session_service.rotate_cookie(id) {
+                Ok(session) => Response::RotateCookie(SessionDto::from(&session).with_cookie(session.cookie().into())),
+                Err(error) => {
+                    error!("Could not rotate the cookie of session {}: {}", id, error);
+                    Response::Error { message: format!("{}", error) }
+                }
+            },
+            Err(_) => {
+                error!("Invalid session id {} provided", id);
+                Response::Error { message: format!("Invalid session id {} provided", id) }
+            }
+        };
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Creates the `DrainDto` of the drain mode, with the number of remaining sessions.
+    ///
+    /// # Returns
+    /// The `DrainDto` for the drain mode.
+    fn create_drain_dto(&self) -> DrainDto {
+        let sessions = self.session_service.get_all().map_or(0, |sessions| sessions.len());
+        DrainDto::new(self.session_service.drain_state().as_ref(), sessions)
+    }
+
+    /// Handles requests to enable the drain mode.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `allow_existing` - Whether the users that already have a session can still log in to it.
+    /// * `deadline` - The unix timestamp at which the remaining sessions are terminated, if any.
+    fn handle_drain_request(&self, rep_socket: &zmq::Socket, allow_existing: bool, deadline: Option<i64>) {
+        let response = match self.session_service.drain(allow_existing, deadline) {
+            Ok(_) => Response::Drain(self.create_drain_dto()),
+            Err(error) => {
+                error!("Could not drain the host: {}", error);
+                Response::Error { message: format!("{}", error) }
+            }
+        };
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Handles requests to disable the drain mode.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    fn handle_undrain_request(&self, rep_socket: &zmq::Socket) {
+        let response = match self.session_service.undrain() {
+            Ok(_) => Response::Undrain(self.create_drain_dto()),
+            Err(error) => {
+                error!("Could not stop draining the host: {}", error);
+                Response::Error { message: format!("{}", error) }
+            }
+        };
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Handles requests to retrieve the drain mode.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    fn handle_drain_status_request(&self, rep_socket: &zmq::Socket) {
+        let response = Response::DrainStatus(self.create_drain_dto());
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Handles requests to show a desktop notification in sessions. A session in which the notification cannot be shown
+    /// does not prevent it from being shown in the others: the result of each session is returned.
+    ///
+    /// # Arguments
+    /// * `rep_socket` - The ZeroMQ reply socket.
+    /// * `ids` - The IDs of the sessions, if the notification is only shown in these sessions.
+    /// * `filter` - The criteria of the sessions.
+    /// * `all` - Whether the notification is shown in all the sessions when no IDs or criteria are given.
+    /// * `notification` - The notification.
+    fn handle_notify_request(&self, rep_socket: &zmq::Socket, ids: Vec<String>, filter: SessionFilter, all: bool, notification: Notification) {
+        let response = match self.get_notified_sessions(&ids, &filter, all) {
+            Ok(sessions) => match self.session_service.notify(&sessions, &notification) {
+                Ok(results) => {
+                    let results = sessions
+                        .iter()
+                        .zip(results)
+                        .map(|(session, result)| {
+                            let error = result.err().map(|error| {
+                                warn!("Could not show the notification in session {}: {}", session.id(), error);
+                                format!("{}", error)
+                            });
+                            NotificationDto::new(session.id().to_string(), session.username().into(), error)
+                        })
+                        .collect();
+                    Response::Notify(results)
+                }
+                Err(error) => {
+                    error!("Could not show the notification: {}", error);
+                    Response::Error { message: format!("{}", error) }
+                }
+            },
+            Err(message) => {
+                warn!("Rejected a notify request: {}", message);
+                Response::Error { message }
+            }
+        };
+
+        let json = self.encoder.encode(response).unwrap_or_else(|| "".into());
+        if let Err(error) = rep_socket.send(&json[..], 0) {
+            error!("Failed to send response message: {}", error);
+        }
+    }
+
+    /// Retrieves the sessions in which a notification is shown: those with the given IDs (and matching the filter),
+    /// otherwise those matching the filter.
+    ///
+    /// # Arguments
+    /// * `ids` - The IDs of the sessions, if the notification is only shown in these sessions.
+    /// * `filter` - The criteria of the sessions.
+    /// * `all` - Whether all the sessions are notified when no IDs or criteria are given.
+    ///
+    /// # Returns
+    /// A `Result` containing the sessions or the message explaining why the request is rejected.
+    fn get_notified_sessions(&self, ids: &[String], filter: &SessionFilter, all: bool) -> Result<Vec<Session>, String> {
+        if ids.is_empty() && filter.is_empty() && !all {
+            return Err("Session ids or a filter are required to notify sessions, or all to notify all the sessions".into());
+        }
+
+        let mut sessions = self.get_filtered_sessions(filter);
+        if !ids.is_empty() {
+            let ids = ids
+                .iter()
+                .map(|id| Uuid::from_str(id).map_err(|_| format!("Invalid session id {} provided", id)))
+                .collect::<Result<Vec<Uuid>, String>>()?;
+            if let Some(id) = ids.iter().find(|id| self.session_service.get_by_id(id).is_none()) {
+                return Err(format!("Session {} not found", id));
+            }
+            sessions.retain(|session| ids.contains(session.id()));
+        }
+        Ok(sessions)
+    }
 }
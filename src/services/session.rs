@@ -1,18 +1,33 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use chrono::Duration;
 use nix::unistd::User;
+use pam_client::env_list::EnvList;
 use uuid::Uuid;
 
 use crate::{
-    authentication::{Authenticator, Credentials},
-    common::{Account, ApplicationError, Session, ScreenResolution},
+    authentication::{AccessControl, Authenticator, Credentials, Lockout, LoginThrottle, ReconnectionTokenStore, TicketVerifier, TotpStore},
+    common::{Account, ApplicationError, Desktop, Grant, Notification, NotificationUrgency, RevalidationAction, RevalidationSettings, Session, SessionOptions, SessionState, ScreenResolution},
 };
 
-use super::XorgService;
+use super::{DrainService, DrainState, XorgService};
 
 /// The `SessionService` struct provides functionality for managing user sessions,
 /// including creating, retrieving, and terminating sessions.
 pub struct SessionService {
     authenticator: Authenticator,
     xorg_service: XorgService,
+    ticket_verifier: Option<TicketVerifier>,
+    reconnection_tokens: Option<ReconnectionTokenStore>,
+    login_throttle: Option<LoginThrottle>,
+    access_control: Option<AccessControl>,
+    totp_store: Option<TotpStore>,
+    revalidation: Option<RevalidationSettings>,
+    drain_service: DrainService,
+    warned_sessions: Mutex<HashSet<Uuid>>,
+    expiry_warned_sessions: Mutex<HashSet<Uuid>>,
+    flagged_sessions: Mutex<HashMap<Uuid, String>>,
 }
 
 impl SessionService {
@@ -21,15 +36,40 @@ impl SessionService {
     /// # Arguments
     /// * `authenticator` - The authenticator for user authentication.
     /// * `xorg_service` - The Xorg service for managing Xorg sessions.
+    /// * `ticket_verifier` - The verifier for signed login tickets, if tickets are enabled.
+    /// * `reconnection_tokens` - The store of reconnection tokens, if reconnection is enabled.
+    /// * `login_throttle` - The rate limiter for login attempts, if rate limiting is enabled.
+    /// * `access_control` - The user and group access rules, if configured.
+    /// * `totp_store` - The store of TOTP secrets, if the built-in second factor is enabled.
+    /// * `revalidation` - The settings of the periodic revalidation of the session users, if enabled.
+    /// * `drain_service` - The drain mode of the session manager.
     ///
     /// # Returns
     /// A new `SessionService` instance.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(authenticator: Authenticator, 
-               xorg_service: XorgService
+               xorg_service: XorgService,
+               ticket_verifier: Option<TicketVerifier>,
+               reconnection_tokens: Option<ReconnectionTokenStore>,
+               login_throttle: Option<LoginThrottle>,
+               access_control: Option<AccessControl>,
+               totp_store: Option<TotpStore>,
+               revalidation: Option<RevalidationSettings>,
+               drain_service: DrainService,
     ) -> Self {
         Self {
             authenticator,
             xorg_service,
+            ticket_verifier,
+            reconnection_tokens,
+            login_throttle,
+            access_control,
+            totp_store,
+            revalidation,
+            drain_service,
+            warned_sessions: Mutex::new(HashSet::new()),
+            expiry_warned_sessions: Mutex::new(HashSet::new()),
+            flagged_sessions: Mutex::new(HashMap::new()),
         }
     }
 
@@ -38,42 +78,275 @@ impl SessionService {
     /// # Arguments
     /// * `credentials` - The user's credentials.
     /// * `resolution` - The screen resolution for the session.
+    /// * `options` - The options chosen at login, such as the display server and the desktop.
     ///
     /// # Returns
     /// A `Result` containing the created `Session` or an `ApplicationError`.
-    pub fn create_session(&self, credentials: &Credentials, resolution: ScreenResolution) -> Result<Session, ApplicationError> {
-        return match self.authenticator.authenticate(credentials) {
-            Ok(environment) => {
+    pub fn create_session(&self, credentials: &Credentials, resolution: ScreenResolution, options: SessionOptions) -> Result<Session, ApplicationError> {
+        if let Some(login_throttle) = &self.login_throttle {
+            if let Err(error) = login_throttle.check(credentials.username()) {
+                audit!("Rejected login attempt for user {}: {}", credentials.username(), error.message());
+                return Err(error);
+            }
+        }
+
+        match self.authenticator.authenticate(credentials) {
+            Ok(authentication) => {
                 debug!("Successfully authenticated user: {}", &credentials.username());
-                if let Ok(Some(user)) = User::from_name(credentials.username()) {
-                    debug!("Found user: {}", &credentials.username());
-                    if let Some(account) = Account::from_user(user) {
-
-                        // if the user already has an x session running then exit early...
-                        if let Some(session) = self.xorg_service.get_session_for_user(account.uid()) {
-                            debug!("User {} already has a session {}", &credentials.username(), session.id());
-                            return Ok(session);
-                        }
 
-                        let webx_user = User::from_name("webx").unwrap().unwrap();
-                        // create the necessary configuration files
-                        if let Err(error) = self.xorg_service.create_user_files(&account, &webx_user) {
-                            return Err(ApplicationError::session(format!("Error occurred setting up the configuration for a session {}", error)));
+                // verify the second factor once the password has been accepted, before the PAM session is opened
+                if let Some(totp_store) = &self.totp_store {
+                    if let Err(error) = totp_store.verify(credentials.username(), credentials.otp()) {
+                        audit!("Failed second factor for user {}: {}", credentials.username(), error.message());
+                        if let Some(login_throttle) = &self.login_throttle {
+                            login_throttle.record_failure(credentials.username());
                         }
-
-                        // finally, let's launch the x server...
-                        return self.xorg_service.execute(&account, &webx_user, resolution, environment);
+                        return Err(ApplicationError::session(format!("Error authenticating user {}", error)));
                     }
-                    return Err(ApplicationError::session(format!("User {} is invalid. check they have a home directory?", credentials.username())));
                 }
-                Err(ApplicationError::session(format!("Could not find user {}", credentials.username())))
+
+                let environment = authentication
+                    .open_session()
+                    .map_err(|error| ApplicationError::session(format!("Error opening the session of user {}", error)))?;
+
+                audit!("User {} logged in", credentials.username());
+                if let Some(login_throttle) = &self.login_throttle {
+                    login_throttle.record_success(credentials.username());
+                }
+                self.launch_session(credentials.username(), resolution, environment, options)
             }
             Err(error) => {
+                audit!("Failed login attempt for user {}", credentials.username());
+                if let Some(login_throttle) = &self.login_throttle {
+                    login_throttle.record_failure(credentials.username());
+                }
                 Err(ApplicationError::session(format!("Error authenticating user {}", error)))
             }
         }
     }
 
+    /// Creates a new session for a user that has been pre-authenticated by a portal and holds a signed login ticket.
+    /// The PAM password step is skipped but the account and session stages are still run.
+    ///
+    /// # Arguments
+    /// * `ticket` - The encoded login ticket.
+    /// * `resolution` - The screen resolution for the session.
+    /// * `options` - The options chosen at login, such as the display server and the desktop.
+    ///
+    /// # Returns
+    /// A `Result` containing the created `Session` or an `ApplicationError`.
+    pub fn create_session_with_ticket(&self, ticket: &str, resolution: ScreenResolution, options: SessionOptions) -> Result<Session, ApplicationError> {
+        let ticket_verifier = self.ticket_verifier
+            .as_ref()
+            .ok_or_else(|| ApplicationError::authentication("Login tickets are not enabled"))?;
+
+        let ticket = ticket_verifier.verify(ticket)
+            .map_err(|error| ApplicationError::session(format!("Error verifying login ticket {}", error)))?;
+
+        match self.authenticator.authorize(ticket.username()) {
+            Ok(environment) => {
+                debug!("Successfully authorized user with login ticket: {}", ticket.username());
+                audit!("User {} logged in with a login ticket", ticket.username());
+                self.launch_session(ticket.username(), resolution, environment, options)
+            }
+            Err(error) => {
+                Err(ApplicationError::session(format!("Error authorizing user {}", error)))
+            }
+        }
+    }
+
+    /// Launches (or returns the existing) session for an authenticated user.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the authenticated user.
+    /// * `resolution` - The screen resolution for the session.
+    /// * `environment` - The environment variables provided by PAM.
+    /// * `options` - The options chosen at login, such as the display server and the desktop.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Session` or an `ApplicationError`.
+    fn launch_session(&self, username: &str, resolution: ScreenResolution, environment: EnvList, options: SessionOptions) -> Result<Session, ApplicationError> {
+        if let Ok(Some(user)) = User::from_name(username) {
+            debug!("Found user: {}", username);
+            if let Some(account) = Account::from_user(user) {
+
+                // verify the user is permitted to start a desktop on this host
+                if let Some(access_control) = &self.access_control {
+                    if let Err(error) = access_control.check(&account) {
+                        audit!("Access denied to user {}: {}", username, error.message());
+                        return Err(ApplicationError::session(format!("Access denied {}", error)));
+                    }
+                }
+
+                // while draining, only the users that already have a session may log in (if permitted)
+                let existing_session = self.xorg_service.get_session_for_user(account.uid());
+                if let Err(error) = self.drain_service.check_login(existing_session.is_some()) {
+                    audit!("Rejected login of user {}: {}", username, error.message());
+                    return Err(error);
+                }
+
+                // if the user already has an x session running then exit early...
+                if let Some(session) = existing_session {
+                    debug!("User {} already has a session {}", username, session.id());
+                    // the user qualifies again so the session is no longer flagged
+                    if let Ok(mut flagged_sessions) = self.flagged_sessions.lock() {
+                        flagged_sessions.remove(session.id());
+                    }
+                    if self.xorg_service.resize_on_login() && session.resolution() != &resolution {
+                        match self.xorg_service.resize(session.id(), resolution) {
+                            Ok(session) => return Ok(session),
+                            Err(error) => warn!("Could not resize session {} on login: {}", session.id(), error),
+                        }
+                    }
+                    return Ok(session);
+                }
+
+                let webx_user = User::from_name("webx").unwrap().unwrap();
+                // create the necessary configuration files
+                if let Err(error) = self.xorg_service.create_user_files(&account, &webx_user) {
+                    return Err(ApplicationError::session(format!("Error occurred setting up the configuration for a session {}", error)));
+                }
+
+                // finally, let's launch the x server...
+                return self.xorg_service.execute(&account, &webx_user, resolution, environment, &options);
+            }
+            return Err(ApplicationError::session(format!("User {} is invalid. check they have a home directory?", username)));
+        }
+        Err(ApplicationError::session(format!("Could not find user {}", username)))
+    }
+
+    /// Issues a reconnection token for a session, if reconnection is enabled.
+    ///
+    /// # Arguments
+    /// * `session` - The session the token is bound to.
+    ///
+    /// # Returns
+    /// An `Option` containing the token, or `None` if reconnection is disabled.
+    pub fn issue_reconnection_token(&self, session: &Session) -> Option<String> {
+        self.reconnection_tokens
+            .as_ref()
+            .and_then(|reconnection_tokens| reconnection_tokens.issue(session.id()))
+    }
+
+    /// Retrieves an existing session using a reconnection token. The token is consumed.
+    ///
+    /// # Arguments
+    /// * `token` - The reconnection token.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Session` the token is bound to or an `ApplicationError`.
+    pub fn reconnect(&self, token: &str) -> Result<Session, ApplicationError> {
+        let reconnection_tokens = self.reconnection_tokens
+            .as_ref()
+            .ok_or_else(|| ApplicationError::authentication("Reconnection tokens are not enabled"))?;
+
+        let session_id = reconnection_tokens
+            .redeem(token)
+            .ok_or_else(|| ApplicationError::authentication("Invalid or expired reconnection token"))?;
+
+        if let Some(reason) = self.revalidation_failure(&session_id) {
+            return Err(ApplicationError::authentication(format!("Session {} is no longer permitted: {}", session_id, reason)));
+        }
+
+        let session = self.xorg_service
+            .get_by_id(&session_id)
+            .ok_or_else(|| ApplicationError::session(format!("Session {} no longer exists", session_id)))?;
+
+        // apply the checks of a login returning an existing session
+        if let Some(access_control) = &self.access_control {
+            let account = User::from_name(session.username())
+                .ok()
+                .flatten()
+                .and_then(Account::from_user)
+                .ok_or_else(|| ApplicationError::authentication(format!("User {} no longer exists", session.username())))?;
+            if let Err(error) = access_control.check(&account) {
+                audit!("Rejected reconnection of user {} to session {}: {}", session.username(), session_id, error.message());
+                return Err(ApplicationError::session(format!("Access denied {}", error)));
+            }
+        }
+        if let Err(error) = self.drain_service.check_login(true) {
+            audit!("Rejected reconnection of user {} to session {}: {}", session.username(), session_id, error.message());
+            return Err(error);
+        }
+
+        Ok(session)
+    }
+
+    /// Retrieves the usernames that are currently locked out after too many failed login attempts.
+    ///
+    /// # Returns
+    /// A vector of `Lockout` instances (empty if rate limiting is disabled).
+    pub fn get_lockouts(&self) -> Vec<Lockout> {
+        self.login_throttle
+            .as_ref()
+            .map(|login_throttle| login_throttle.lockouts())
+            .unwrap_or_default()
+    }
+
+    /// Clears the lockout of a username, or of all usernames.
+    ///
+    /// # Arguments
+    /// * `username` - The username to clear, or `None` to clear all usernames.
+    ///
+    /// # Returns
+    /// The number of lockouts cleared.
+    pub fn clear_lockouts(&self, username: Option<&str>) -> usize {
+        if let Some(login_throttle) = &self.login_throttle {
+            let cleared = login_throttle.clear(username);
+            audit!("Cleared {} lockouts for {}", cleared, username.unwrap_or("all users"));
+            return cleared;
+        }
+        0
+    }
+
+    /// Enrolls a user in the built-in TOTP second factor, replacing any existing secret.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the user.
+    ///
+    /// # Returns
+    /// A `Result` containing the otpauth URI to register in an authenticator application, or an `ApplicationError`.
+    pub fn enroll_totp(&self, username: &str) -> Result<String, ApplicationError> {
+        let totp_store = self.totp_store
+            .as_ref()
+            .ok_or_else(|| ApplicationError::authentication("TOTP is not enabled"))?;
+
+        if User::from_name(username).ok().flatten().is_none() {
+            return Err(ApplicationError::authentication(format!("Could not find user {}", username)));
+        }
+
+        let uri = totp_store.enroll(username)?;
+        audit!("User {} enrolled for TOTP", username);
+        Ok(uri)
+    }
+
+    /// Removes the TOTP enrollment of a user.
+    ///
+    /// # Arguments
+    /// * `username` - The username of the user.
+    ///
+    /// # Returns
+    /// A `Result` indicating whether the user was enrolled, or an `ApplicationError`.
+    pub fn reset_totp(&self, username: &str) -> Result<bool, ApplicationError> {
+        let totp_store = self.totp_store
+            .as_ref()
+            .ok_or_else(|| ApplicationError::authentication("TOTP is not enabled"))?;
+
+        let reset = totp_store.reset(username)?;
+        if reset {
+            audit!("TOTP enrollment of user {} reset", username);
+        }
+        Ok(reset)
+    }
+
+    /// Retrieves the desktop environments offered to the users.
+    ///
+    /// # Returns
+    /// A vector of `Desktop` instances.
+    pub fn get_desktops(&self) -> Vec<Desktop> {
+        self.xorg_service.get_desktops()
+    }
+
     /// Retrieves all active sessions.
     ///
     /// # Returns
@@ -87,6 +360,104 @@ impl SessionService {
         None
     }
 
+    /// Retrieves a session by its unique identifier.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    ///
+    /// # Returns
+    /// An `Option` containing the `Session` if found, or `None` otherwise.
+    pub fn get_by_id(&self, id: &Uuid) -> Option<Session> {
+        self.xorg_service.get_by_id(id)
+    }
+
+    /// Returns the state of a session: flagged if its user no longer qualifies for it, otherwise active.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    ///
+    /// # Returns
+    /// The `SessionState` of the session.
+    pub fn get_state(&self, id: &Uuid) -> SessionState {
+        match self.revalidation_failure(id) {
+            Some(_) => SessionState::Flagged,
+            None => SessionState::Active,
+        }
+    }
+
+    /// Changes the screen resolution of a running session.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    /// * `resolution` - The new screen resolution.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated `Session` or an `ApplicationError`.
+    pub fn resize(&self, id: Uuid, resolution: ScreenResolution) -> Result<Session, ApplicationError> {
+        self.xorg_service.resize(&id, resolution)
+    }
+
+    /// Replaces the cookie given to the clients of a session, disconnecting those using the previous cookie.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated `Session` or an `ApplicationError`.
+    pub fn rotate_cookie(&self, id: Uuid) -> Result<Session, ApplicationError> {
+        self.xorg_service.rotate_cookie(&id)
+    }
+
+    /// Determines whether a cookie is the one given to the clients of a session, which is only returned to its owner
+    /// (and to admin requests).
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    /// * `cookie` - The hexadecimal cookie to verify.
+    ///
+    /// # Returns
+    /// `true` if the session exists and the cookie matches, otherwise `false`.
+    pub fn is_session_cookie(&self, id: &Uuid, cookie: &str) -> bool {
+        match self.xorg_service.get_by_id(id) {
+            Some(session) => {
+                let expected = session.cookie().as_bytes();
+                // compare all the characters so that the time taken does not depend on the matching prefix
+                !expected.is_empty()
+                    && expected.len() == cookie.len()
+                    && expected.iter().zip(cookie.bytes()).fold(0, |difference, (a, b)| difference | (a ^ b)) == 0
+            }
+            None => false,
+        }
+    }
+
+    /// Grants another user access to a session.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    /// * `grantee` - The username of the user granted access.
+    /// * `view_only` - Whether the grantee may only view the session.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Grant` or an `ApplicationError`.
+    pub fn grant(&self, id: Uuid, grantee: &str, view_only: bool) -> Result<Grant, ApplicationError> {
+        if User::from_name(grantee).ok().flatten().is_none() {
+            return Err(ApplicationError::session(format!("Could not find user {}", grantee)));
+        }
+        self.xorg_service.grant(&id, grantee, view_only)
+    }
+
+    /// Revokes the access to a session granted to a user.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    /// * `grantee` - The username of the user granted access.
+    ///
+    /// # Returns
+    /// A `Result` indicating whether the user was granted access, or an `ApplicationError`.
+    pub fn revoke_grant(&self, id: Uuid, grantee: &str) -> Result<bool, ApplicationError> {
+        self.xorg_service.revoke_grant(&id, grantee)
+    }
+
     /// Terminates a session by its unique identifier.
     ///
     /// # Arguments
@@ -100,12 +471,16 @@ impl SessionService {
             // the session will be automatically removed by the clean up procedure
             session.window_manager().kill()?;
             session.xorg().kill()?;
+            if let Some(reconnection_tokens) = &self.reconnection_tokens {
+                reconnection_tokens.revoke(&id);
+            }
+            audit!("Session {} of user {} logged out", id, session.username());
             return Ok(());
         }
         Err(ApplicationError::session(format!("Session {} not found", id)))
     }
 
-    /// Terminates all active sessions.
+    /// Terminates all active sessions and removes their Xauthority files, including those of the users granted access.
     ///
     /// # Returns
     /// A `Result` indicating success or an `ApplicationError`.
@@ -114,11 +489,285 @@ impl SessionService {
             for session in sessions {
                 session.window_manager().kill()?;
                 session.xorg().kill()?;
-            } 
+                self.xorg_service.remove_authority_files(&session);
+            }
+        }
+        Ok(())
+    }
+
+    /// Terminates the sessions whose users are no longer permitted by the login windows, if enabled.
+    /// Users are warned (once) when their session will be terminated within the configured warning period.
+    pub fn enforce_login_windows(&self) {
+        let access_control = match &self.access_control {
+            Some(access_control) if access_control.terminate_outside_windows() => access_control,
+            _ => return,
+        };
+
+        let sessions = self.get_all().unwrap_or_default();
+        let now = chrono::Local::now().naive_local();
+        let warning_minutes = access_control.termination_warning();
+        let warning_at = now + Duration::minutes(warning_minutes as i64);
+        let mut sessions_to_warn = vec![];
+
+        for session in &sessions {
+            let account = match User::from_name(session.username()) {
+                Ok(Some(user)) => Account::from_user(user),
+                _ => None,
+            };
+            let account = match account {
+                Some(account) => account,
+                None => continue,
+            };
+
+            if let Err(error) = access_control.check_login_windows(&account, &now) {
+                audit!("Terminating session {} of user {}: {}", session.id(), session.username(), error.message());
+                if let Err(error) = self.kill_by_id(*session.id()) {
+                    error!("Could not terminate session {}: {}", session.id(), error);
+                }
+
+            } else if let Err(error) = access_control.check_login_windows(&account, &warning_at) {
+                if let Ok(mut warned_sessions) = self.warned_sessions.lock() {
+                    if warned_sessions.insert(*session.id()) {
+                        audit!("Session {} of user {} will be terminated in {} minutes: {}", session.id(), session.username(), warning_minutes, error.message());
+                        sessions_to_warn.push(session.clone());
+                    }
+                }
+
+            } else if let Ok(mut warned_sessions) = self.warned_sessions.lock() {
+                warned_sessions.remove(session.id());
+            }
+        }
+
+        self.warn_sessions(&sessions_to_warn, &format!("Your session will be terminated in {} minutes: the login window is closing", warning_minutes));
+
+        // forget the warnings of sessions that no longer exist
+        if let Ok(mut warned_sessions) = self.warned_sessions.lock() {
+            warned_sessions.retain(|id| sessions.iter().any(|session| session.id() == id));
+        }
+    }
+
+    /// Returns the number of seconds between two revalidations of the session users, if revalidation is enabled.
+    pub fn revalidation_interval(&self) -> Option<u64> {
+        self.revalidation.as_ref().map(|revalidation| revalidation.interval())
+    }
+
+    /// Returns the reason why a session was flagged by the revalidation, if it was.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    ///
+    /// # Returns
+    /// An `Option` containing the reason the user of the session no longer qualifies.
+    pub fn revalidation_failure(&self, id: &Uuid) -> Option<String> {
+        self.flagged_sessions
+            .lock()
+            .ok()
+            .and_then(|flagged_sessions| flagged_sessions.get(id).cloned())
+    }
+
+    /// Re-checks the user of each session through NSS, the PAM account stage and the access rules,
+    /// and terminates or flags the sessions whose user no longer qualifies (removed, locked or expired accounts,
+    /// or users no longer permitted by the access rules).
+    pub fn revalidate_sessions(&self) {
+        let action = match &self.revalidation {
+            Some(revalidation) => revalidation.action(),
+            None => return,
+        };
+
+        let sessions = self.get_all().unwrap_or_default();
+        for session in &sessions {
+            match self.validate_user(session) {
+                Ok(_) => {
+                    if let Ok(mut flagged_sessions) = self.flagged_sessions.lock() {
+                        if flagged_sessions.remove(session.id()).is_some() {
+                            audit!("Session {} of user {} is permitted again", session.id(), session.username());
+                        }
+                    }
+                }
+                Err(error) if action == RevalidationAction::Terminate => {
+                    audit!("Terminating session {} of user {}: {}", session.id(), session.username(), error.message());
+                    if let Err(error) = self.kill_by_id(*session.id()) {
+                        error!("Could not terminate session {}: {}", session.id(), error);
+                    }
+                }
+                Err(error) => {
+                    if let Ok(mut flagged_sessions) = self.flagged_sessions.lock() {
+                        if flagged_sessions.insert(*session.id(), error.message().into()).is_none() {
+                            audit!("Flagging session {} of user {}: {}", session.id(), session.username(), error.message());
+                        }
+                    }
+                    if let Some(reconnection_tokens) = &self.reconnection_tokens {
+                        reconnection_tokens.revoke(session.id());
+                    }
+                }
+            }
+        }
+
+        // forget the flags of sessions that no longer exist
+        if let Ok(mut flagged_sessions) = self.flagged_sessions.lock() {
+            flagged_sessions.retain(|id, _| sessions.iter().any(|session| session.id() == id));
+        }
+    }
+
+    /// Determines whether the user of a session still qualifies for it.
+    ///
+    /// # Arguments
+    /// * `session` - The session to validate.
+    ///
+    /// # Returns
+    /// A `Result` indicating the user qualifies or an `ApplicationError` explaining why it does not.
+    fn validate_user(&self, session: &Session) -> Result<(), ApplicationError> {
+        let user = User::from_name(session.username())
+            .ok()
+            .flatten()
+            .ok_or_else(|| ApplicationError::authentication(format!("User {} no longer exists", session.username())))?;
+
+        if user.uid.as_raw() != session.uid() {
+            return Err(ApplicationError::authentication(format!("User {} no longer has the uid {}", session.username(), session.uid())));
+        }
+
+        let account = Account::from_user(user)
+            .ok_or_else(|| ApplicationError::authentication(format!("User {} is no longer valid", session.username())))?;
+
+        self.authenticator
+            .validate_account(session.username())
+            .map_err(|error| ApplicationError::authentication(format!("Account of user {} is no longer valid: {}", session.username(), error)))?;
+
+        if let Some(access_control) = &self.access_control {
+            access_control.check_rules(&account)?;
         }
+
         Ok(())
     }
 
+    /// Enables the drain mode: new logins are rejected and, if a deadline is given, the remaining sessions are terminated
+    /// when it is reached.
+    ///
+    /// # Arguments
+    /// * `allow_existing` - Whether the users that already have a session can still log in to it.
+    /// * `deadline` - The unix timestamp at which the remaining sessions are terminated, if any.
+    ///
+    /// # Returns
+    /// A `Result` containing the `DrainState` or an `ApplicationError`.
+    pub fn drain(&self, allow_existing: bool, deadline: Option<i64>) -> Result<DrainState, ApplicationError> {
+        let state = self.drain_service.start(allow_existing, deadline)?;
+        match deadline {
+            Some(deadline) => audit!("Draining the host until {}", deadline),
+            None => audit!("Draining the host"),
+        }
+        Ok(state)
+    }
+
+    /// Disables the drain mode.
+    ///
+    /// # Returns
+    /// A `Result` indicating whether the session manager was draining, or an `ApplicationError`.
+    pub fn undrain(&self) -> Result<bool, ApplicationError> {
+        let stopped = self.drain_service.stop()?;
+        if stopped {
+            audit!("Stopped draining the host");
+        }
+        Ok(stopped)
+    }
+
+    /// Returns the drain mode, if the session manager is draining.
+    pub fn drain_state(&self) -> Option<DrainState> {
+        self.drain_service.state()
+    }
+
+    /// Warns the users (once) when the drain deadline approaches and terminates the remaining sessions when it is reached.
+    pub fn enforce_drain(&self) {
+        let deadline = match self.drain_service.state().and_then(|state| state.deadline()) {
+            Some(deadline) => deadline,
+            None => return,
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        if now >= deadline {
+            for session in self.get_all().unwrap_or_default() {
+                audit!("Terminating session {} of user {}: the drain deadline has been reached", session.id(), session.username());
+                if let Err(error) = self.kill_by_id(*session.id()) {
+                    error!("Could not terminate session {}: {}", session.id(), error);
+                }
+            }
+
+        } else if now >= deadline - self.drain_service.warning() as i64 * 60 && self.drain_service.set_warned() {
+            let minutes = (deadline - now + 59) / 60;
+            let sessions = self.get_all().unwrap_or_default();
+            for session in &sessions {
+                audit!("Session {} of user {} will be terminated in {} minutes: the host is being drained", session.id(), session.username(), minutes);
+            }
+            self.warn_sessions(&sessions, &format!("Your session will be terminated in {} minutes: the host is going into maintenance", minutes));
+        }
+    }
+
+    /// Shows a desktop notification in sessions.
+    ///
+    /// # Arguments
+    /// * `sessions` - The sessions in which the notification is shown.
+    /// * `notification` - The notification.
+    ///
+    /// # Returns
+    /// A `Result` containing the result of the notification of each session, in the order of the sessions,
+    /// or an `ApplicationError` if the notification is invalid.
+    pub fn notify(&self, sessions: &[Session], notification: &Notification) -> Result<Vec<Result<(), ApplicationError>>, ApplicationError> {
+        notification.validate()?;
+        let results = self.xorg_service.notify(sessions, notification);
+        audit!("Notification \"{}\" shown in {} of {} sessions", notification.summary(), results.iter().filter(|result| result.is_ok()).count(), sessions.len());
+        Ok(results)
+    }
+
+    /// Warns the users of sessions that their session will be terminated, with a critical desktop notification.
+    ///
+    /// # Arguments
+    /// * `sessions` - The sessions whose users are warned.
+    /// * `message` - The warning.
+    fn warn_sessions(&self, sessions: &[Session], message: &str) {
+        if sessions.is_empty() {
+            return;
+        }
+        let notification = Notification::new("Session termination".into(), Some(message.into()), NotificationUrgency::Critical);
+        for (session, result) in sessions.iter().zip(self.xorg_service.notify(sessions, &notification)) {
+            if let Err(error) = result {
+                warn!("Could not warn the user of session {}: {}", session.id(), error);
+            }
+        }
+    }
+
+    /// Terminates the sessions that have reached the maximum duration of their profile.
+    /// Users are warned (once) when their session will be terminated within the warning period of the profile.
+    pub fn enforce_max_durations(&self) {
+        let sessions = self.get_all().unwrap_or_default();
+        let now = chrono::Utc::now().timestamp();
+
+        for session in &sessions {
+            let expires = match session.expires() {
+                Some(expires) => expires,
+                None => continue,
+            };
+
+            if expires <= now {
+                audit!("Terminating session {} of user {}: the maximum duration of the profile {} has been reached", session.id(), session.username(), session.profile().unwrap_or_default());
+                if let Err(error) = self.kill_by_id(*session.id()) {
+                    error!("Could not terminate session {}: {}", session.id(), error);
+                }
+
+            } else if now >= expires - session.expiry_warning() as i64 * 60 {
+                let newly_warned = self.expiry_warned_sessions.lock().is_ok_and(|mut warned_sessions| warned_sessions.insert(*session.id()));
+                if newly_warned {
+                    let minutes = (expires - now + 59) / 60;
+                    audit!("Session {} of user {} will be terminated in {} minutes: the maximum duration of the profile {} is being reached", session.id(), session.username(), minutes, session.profile().unwrap_or_default());
+                    self.warn_sessions(std::slice::from_ref(session), &format!("Your session will be terminated in {} minutes: it is reaching its maximum duration", minutes));
+                }
+            }
+        }
+
+        // forget the warnings of sessions that no longer exist
+        if let Ok(mut warned_sessions) = self.expiry_warned_sessions.lock() {
+            warned_sessions.retain(|id| sessions.iter().any(|session| session.id() == id));
+        }
+    }
+
     /// Cleans up zombie sessions by removing sessions whose processes are no longer running.
     pub fn clean_up(&self) {
         if self.xorg_service.clean_up() > 0 {
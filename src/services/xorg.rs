@@ -1,21 +1,50 @@
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
+use std::os::unix::fs::MetadataExt;
 use std::os::unix::prelude::CommandExt;
-use std::process::Command;
-use std::sync::Mutex;
+use std::path::Path;
+use std::io::{Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::str::FromStr;
+use std::sync::{mpsc, Mutex};
 use std::{thread, time};
 
+use nix::sys::resource::{setrlimit, Resource};
 use nix::unistd::{User, Gid, Uid, setgroups, setgid, setuid};
 use pam_client::env_list::EnvList;
 use rand::Rng;
 use uuid::Uuid;
 
-use crate::common::{Account, ApplicationError, ProcessHandle, ScreenResolution, Session, XorgSettings};
-use crate::fs::{chmod, chown, mkdir, touch};
+use crate::common::{Account, ApplicationError, Desktop, DisplayProperties, DisplayServerKind, Grant, KeyboardLayout, Notification, ProcessHandle, ProfileExitPolicy, ProfileSettings, ResourceLimitSettings, ScreenResolution, Session, SessionOptions, XorgSettings};
+use crate::fs::{chmod, chown, mkdir, write_xauthority};
+
+use super::display::{create_display_server, DisplayServer, SecurityClient};
+
+/// The environment variables managed by the session manager, which clients may not provide.
+const RESERVED_ENVIRONMENT: [&str; 18] = [
+    "DISPLAY", "XAUTHORITY", "HOME", "USER", "LOGNAME", "SHELL", "PATH", "XDG_RUNTIME_DIR", "XDG_SESSION_DESKTOP",
+    "XDG_CURRENT_DESKTOP", "DESKTOP_SESSION", "STARTUP", "WEBX_PROFILE", "WEBX_LOCALE", "WEBX_DPI", "GDK_SCALE",
+    "QT_SCALE_FACTOR", "QT_FONT_DPI",
+];
+
+/// The prefixes of the environment variables managed by the session manager or the dynamic loader, which clients may not provide.
+const RESERVED_ENVIRONMENT_PREFIXES: [&str; 2] = ["LD_", "KIOSK_"];
+
+/// The time given to `notify-send` to show a notification in the sessions.
+const NOTIFICATION_TIMEOUT: time::Duration = time::Duration::from_secs(5);
+
+/// The maximum number of labels of a session.
+const MAX_LABELS: usize = 32;
+
+/// The maximum length of the keys and values of the labels of a session.
+const MAX_LABEL_LENGTH: usize = 256;
 
 /// The `XorgService` struct provides functionality for managing Xorg sessions,
 /// including creating, cleaning up, and launching Xorg servers and window managers.
 pub struct XorgService {
     settings: XorgSettings,
+    profiles: Vec<ProfileSettings>,
+    default_profile: Option<String>,
     sessions: Mutex<Vec<Session>>,
 }
 
@@ -24,12 +53,14 @@ impl XorgService {
     ///
     /// # Arguments
     /// * `settings` - The Xorg settings to use for managing sessions.
+    /// * `profiles` - The session profiles that can be chosen at login.
+    /// * `default_profile` - The name of the profile applied when none is chosen at login, if any.
     ///
     /// # Returns
     /// A new `XorgService` instance.
-    pub fn new(settings: XorgSettings) -> Self {
+    pub fn new(settings: XorgSettings, profiles: Vec<ProfileSettings>, default_profile: Option<String>) -> Self {
         let sessions = Mutex::new(Vec::new());
-        Self { settings, sessions }
+        Self { settings, profiles, default_profile, sessions }
     }
 
     /// Retrieves all active sessions.
@@ -63,6 +94,7 @@ impl XorgService {
     }
 
     /// Cleans up zombie sessions by removing sessions whose Xorg processes are no longer running.
+    /// Sessions that end on exit (such as kiosk sessions) are terminated when their window manager has exited.
     ///
     /// # Returns
     /// The number of sessions cleaned up.
@@ -70,10 +102,19 @@ impl XorgService {
         let mut cleaned_up_total = 0;
         if let Ok(mut sessions) = self.sessions.lock() {
             sessions.retain(|session| {
-                if session.xorg().is_running().is_err() {
+                if session.end_on_exit() && session.window_manager().is_running().is_ok() {
+                    audit!("Ending session {} of user {} as its application has exited", session.id(), session.username());
+                    if let Err(error) = session.xorg().kill() {
+                        error!("Could not terminate the display server of session {}: {}", session.id(), error);
+                    }
+                    self.remove_authority_files(session);
+                    cleaned_up_total += 1;
+                    false
+                } else if session.xorg().is_running().is_err() {
                     true
                 } else {
                     error!("Removing session {} as the xorg server is no longer running", session.id());
+                    self.remove_authority_files(session);
                     cleaned_up_total += 1;
                     false
                 }
@@ -98,66 +139,130 @@ impl XorgService {
             .collect()
     }
 
-    /// Creates an Xauth token for a specific display and user.
+    /// Creates an Xauthority file holding the cookie of a display.
     ///
     /// # Arguments
     /// * `display` - The display number.
-    /// * `account` - The user account for which the token is created.
-    /// * `webx_user` - The WebX system user.
+    /// * `file_path` - The path to the Xauthority file.
+    /// * `cookie` - The hexadecimal cookie.
+    /// * `uid` - The user ID owning the file.
+    /// * `gid` - The group ID owning the file.
+    /// * `mode` - The permissions of the file.
     ///
     /// # Returns
     /// A `Result` indicating success or an `ApplicationError`.
-    fn create_token(&self, display: u32, account: &Account, webx_user: &User) -> Result<(), ApplicationError> {
-        debug!("Creating xauth token for display {} and user {}", display, account.username());
-        let cookie = self.create_cookie();
-        let file_path = format!(
-            "{}/{}/Xauthority",
-            self.settings.sessions_path(),
-            account.uid()
-        );
-        let display = format!(":{}", display);
-        Command::new("xauth")
-            .arg("-f")
-            .arg(&file_path)
-            .arg("add")
-            .arg(display)
-            .arg(".")
-            .arg(cookie)
-            .uid(account.uid())
-            .gid(webx_user.gid.as_raw())
-            .output()?;
+    fn create_token(&self, display: u32, file_path: &str, cookie: &str, uid: u32, gid: u32, mode: u32) -> Result<(), ApplicationError> {
+        debug!("Creating xauth token for display {} in {}", display, file_path);
+        write_xauthority(file_path, display, &decode_hex(cookie), uid, gid, mode)
+    }
 
-        chmod(&file_path, 0o640)?;
-        Ok(())
+    /// Returns the paths to the Xauthority files of a session: the one of the display server and the one given to its clients.
+    ///
+    /// # Arguments
+    /// * `session_id` - The unique identifier for the session.
+    /// * `uid` - The user ID of the session owner.
+    ///
+    /// # Returns
+    /// A tuple containing the path to the Xauthority file of the display server and of the clients.
+    fn get_authority_file_paths(&self, session_id: &Uuid, uid: u32) -> (String, String) {
+        let directory = self.settings.sessions_path_for_uid(uid);
+        (
+            format!("{}/{}.server.Xauthority", directory, session_id.simple()),
+            format!("{}/{}.Xauthority", directory, session_id.simple()),
+        )
+    }
+
+    /// Generates the revocable cookie given to the clients of a display with the SECURITY extension.
+    /// If the display server does not support the extension, the cookie of the display server is used instead.
+    ///
+    /// # Arguments
+    /// * `display` - The display number.
+    /// * `server_cookie` - The hexadecimal cookie of the display server.
+    ///
+    /// # Returns
+    /// A tuple containing the hexadecimal cookie and the identifier of its authorization, if it can be revoked.
+    fn create_client_cookie(&self, display: u32, server_cookie: &str) -> (String, Option<u32>) {
+        let socket_path = format!("{}/X{}", self.settings.x11_socket_path(), display);
+        match SecurityClient::connect(&socket_path, &decode_hex(server_cookie)).and_then(|mut client| client.generate(true)) {
+            Ok(authorization) => (encode_hex(authorization.cookie()), Some(authorization.id())),
+            Err(error) => {
+                warn!("Could not generate a revocable cookie for display :{}, the cookie cannot be rotated: {}", display, error);
+                (server_cookie.into(), None)
+            }
+        }
+    }
+
+    /// Removes the Xauthority files of a session, including those of the users granted access.
+    ///
+    /// # Arguments
+    /// * `session` - The session.
+    pub fn remove_authority_files(&self, session: &Session) {
+        let grant_paths = session.grants().iter().map(Grant::xauthority_file_path);
+        for path in vec![session.xauthority_file_path(), session.server_xauthority_file_path()].into_iter().chain(grant_paths) {
+            self.remove_authority_file(path);
+        }
+    }
+
+    /// Removes an Xauthority file, logging a warning if it exists and cannot be removed.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the Xauthority file.
+    fn remove_authority_file(&self, path: &str) {
+        if let Err(error) = fs::remove_file(path) {
+            if error.kind() != std::io::ErrorKind::NotFound {
+                warn!("Could not remove the Xauthority file {}: {}", path, error);
+            }
+        }
+    }
+
+    /// Connects to the display of a session to generate or revoke its authorizations.
+    ///
+    /// # Arguments
+    /// * `session` - The session.
+    ///
+    /// # Returns
+    /// A `Result` containing the `SecurityClient` and the display number, or an `ApplicationError`.
+    fn connect_to_display(&self, session: &Session) -> Result<(SecurityClient, u32), ApplicationError> {
+        let display = session.display_id()
+            .trim_start_matches(':')
+            .parse::<u32>()
+            .map_err(|_| ApplicationError::session(format!("Invalid display {} for session {}", session.display_id(), session.id())))?;
+
+        let socket_path = format!("{}/X{}", self.settings.x11_socket_path(), display);
+        let client = SecurityClient::connect(&socket_path, &decode_hex(session.server_cookie()))?;
+        Ok((client, display))
     }
 
-    /// Spawns an Xorg server process for a session.
+    /// Spawns the display server process for a session.
     ///
     /// # Arguments
     /// * `session_id` - The unique identifier for the session.
+    /// * `display_server` - The display server backend to launch.
     /// * `display` - The display number.
+    /// * `authority_file_path` - The path to the Xauthority file of the display server.
     /// * `resolution` - The screen resolution for the session.
+    /// * `properties` - The DPI and color depth of the display.
     /// * `account` - The user account for the session.
     /// * `environment` - The environment variables for the session.
+    /// * `session_environment` - The environment variables derived from the options chosen at login, overriding those of PAM.
     ///
     /// # Returns
-    /// A `Result` containing the `ProcessHandle` for the Xorg server or an `ApplicationError`.
+    /// A `Result` containing the `ProcessHandle` for the display server or an `ApplicationError`.
+    #[allow(clippy::too_many_arguments)]
     fn spawn_x_server(
         &self,
         session_id: &Uuid,
+        display_server: &dyn DisplayServer,
         display: u32,
+        authority_file_path: &str,
         resolution: &ScreenResolution,
+        properties: &DisplayProperties,
         account: &Account,
         environment: &EnvList,
+        session_environment: &[(String, String)],
     ) -> Result<ProcessHandle, ApplicationError> {
-        debug!("Launching x server on display :{}", display);
-        let authority_file_path = format!(
-            "{}/{}/Xauthority",
-            self.settings.sessions_path(),
-            account.uid()
-        );
+        debug!("Launching {} display server on display :{}", display_server.kind(), display);
         let display = format!(":{}", display);
-        let config = self.settings.config_path();
         let stdout_file = File::create(&format!(
             "{}/{}.xorg.out.log",
             self.settings.log_path(),
@@ -170,27 +275,19 @@ impl XorgService {
         ))?;
 
         let xdg_run_time_dir = format!("{}/{}", self.settings.sessions_path(), account.uid());
-        let (screen_width, screen_height) = resolution.split();
-        let mut command = Command::new("Xorg");
+        let arguments = display_server.arguments(&display, authority_file_path, &xdg_run_time_dir, resolution, properties);
+        let mut command = Command::new(display_server.program());
 
         command
-            .args([
-                display.as_str(),
-                "-auth",
-                authority_file_path.as_str(),
-                "-config",
-                config,
-                "-verbose",
-            ])
+            .args(arguments)
             .env_clear()
             .env("DISPLAY", display)
             .env("XAUTHORITY", authority_file_path)
             .env("HOME", account.home())
-            .env("XORG_RUN_AS_USER_OK", "1")
             .env("XDG_RUNTIME_DIR", xdg_run_time_dir)
-            .env("XRDP_START_WIDTH", screen_width.to_string())
-            .env("XRDP_START_HEIGHT", screen_height.to_string())
+            .envs(display_server.environment(resolution))
             .envs(environment.iter_tuples())
+            .envs(session_environment.iter().cloned())
             .current_dir(account.home())
             .stdout(std::process::Stdio::from(stdout_file))
             .stderr(std::process::Stdio::from(stderr_file));
@@ -217,8 +314,8 @@ impl XorgService {
 
         debug!("Spawning command: {}", format!("{:?}", command).replace('\"', ""));
         ProcessHandle::new(&mut command).map_err(|e| {
-            error!("Failed to spawn Xorg server process: {}", e);
-            ApplicationError::session(format!("Failed to spawn Xorg server: {}", e))
+            error!("Failed to spawn {} display server process: {}", display_server.kind(), e);
+            ApplicationError::session(format!("Failed to spawn {} display server: {}", display_server.kind(), e))
         })
     }
 
@@ -227,20 +324,29 @@ impl XorgService {
     /// # Arguments
     /// * `session_id` - The unique identifier for the session.
     /// * `display` - The display number.
+    /// * `authority_file_path` - The path to the Xauthority file of the display server.
     /// * `account` - The user account for the session.
     /// * `environment` - The environment variables for the session.
+    /// * `session_environment` - The environment variables derived from the options chosen at login, overriding those of PAM.
+    /// * `desktop` - The desktop environment chosen at login, if any.
+    /// * `profile` - The session profile chosen at login, if any.
+    /// * `properties` - The DPI and color depth of the display.
     ///
     /// # Returns
     /// A `Result` containing the `ProcessHandle` for the window manager or an `ApplicationError`.
+    #[allow(clippy::too_many_arguments)]
     fn spawn_window_manager(
         &self,
         session_id: &Uuid,
         display: u32,
+        authority_file_path: &str,
         account: &Account,
         environment: &EnvList,
+        session_environment: &[(String, String)],
+        desktop: Option<&Desktop>,
+        profile: Option<&ProfileSettings>,
+        properties: &DisplayProperties,
     ) -> Result<ProcessHandle, ApplicationError> {
-        let authority_file_path = format!("{}/{}/Xauthority", self.settings.sessions_path(), account.uid());
-
         let display = format!(":{}", display);
         let log_path = self.settings.log_path();
         let stdout_file = File::create(&format!("{}/{}.wm.out.log", log_path, session_id.simple()))?;
@@ -248,7 +354,13 @@ impl XorgService {
 
         let xdg_run_time_dir = self.settings.sessions_path_for_uid(account.uid());
 
-        let mut command = Command::new(self.settings.window_manager());
+        // a kiosk profile runs its application with the kiosk launcher, other profiles may replace the window manager script
+        let program = match profile {
+            Some(profile) if profile.application().is_some() => self.settings.kiosk_launcher(),
+            Some(profile) => profile.window_manager().unwrap_or_else(|| self.settings.window_manager()),
+            None => self.settings.window_manager(),
+        };
+        let mut command = Command::new(program);
 
         command
             .env_clear()
@@ -257,22 +369,62 @@ impl XorgService {
             .env("HOME", account.home())
             .env("XDG_RUNTIME_DIR", xdg_run_time_dir)
             .envs(environment.iter_tuples())
+            .envs(session_environment.iter().cloned())
             .current_dir(account.home())
             .stdout(std::process::Stdio::from(stdout_file))
             .stderr(std::process::Stdio::from(stderr_file));
 
+        // scale the toolkits of HiDPI sessions: GTK by an integer factor (its fonts follow Xft.dpi),
+        // Qt by the exact factor with its font DPI fixed to avoid scaling the fonts twice
+        if properties.dpi() != 96 {
+            let scale = properties.scale();
+            command
+                .env("WEBX_DPI", properties.dpi().to_string())
+                .env("GDK_SCALE", (scale.floor() as u32).max(1).to_string())
+                .env("QT_SCALE_FACTOR", format!("{:.2}", scale))
+                .env("QT_FONT_DPI", "96");
+        }
+
+        // the window manager script starts the chosen desktop with the STARTUP command
+        if let Some(desktop) = desktop {
+            command
+                .env("STARTUP", desktop.exec())
+                .env("DESKTOP_SESSION", desktop.id())
+                .env("XDG_SESSION_DESKTOP", desktop.id())
+                .env("XDG_CURRENT_DESKTOP", desktop.current_desktop());
+        }
+
+        if let Some(profile) = profile {
+            command
+                .env("WEBX_PROFILE", profile.name())
+                .envs(profile.env());
+            if let Some(application) = profile.application() {
+                command
+                    .env("KIOSK_APPLICATION", application)
+                    .env("KIOSK_ON_EXIT", profile.on_exit().to_string());
+                if let Some(window_manager) = profile.window_manager() {
+                    command.env("KIOSK_WINDOW_MANAGER", window_manager);
+                }
+            }
+        }
+
         // Convert u32 groups to Gid and set supplementary groups
         let gids: Vec<Gid> = account.groups().iter().map(|&g| Gid::from_raw(g)).collect();
         let uid = Uid::from_raw(account.uid());
         let gid = Gid::from_raw(account.gid());
+        let limits = profile.map(|profile| self.get_resource_limits(&profile.limits())).unwrap_or_default();
 
         unsafe {
             // The `pre_exec` function is used to set the user and group IDs before executing the command
             // This is necessary to ensure the remote desktop runs with the correct permissions
             // and can access the user's home directory and other resources.
             // Alternative the the .groups method of Command could be used but this requires the nightly/unstable version of rust
+            // The resource limits of the profile are set beforehand, while still root, and are inherited by the desktop processes
             command
                 .pre_exec(move || {
+                    for (resource, limit) in &limits {
+                        setrlimit(*resource, *limit, *limit)?;
+                    }
                     setgroups(&gids)?;
                     setgid(gid)?;
                     setuid(uid)?;
@@ -288,50 +440,55 @@ impl XorgService {
         })
     }
 
-    /// Creates a directory for a session with the specified permissions and ownership.
+    /// Sets the `Xft.dpi` resource of a display, used by the desktop toolkits to scale their fonts.
     ///
     /// # Arguments
-    /// * `path` - The path to the directory.
-    /// * `mode` - The permissions for the directory.
-    /// * `uid` - The user ID to set as the owner.
-    /// * `gid` - The group ID to set as the owner.
+    /// * `display` - The X11 display.
+    /// * `authority_file_path` - The path to the Xauthority file of the session.
+    /// * `dpi` - The resolution of the display in dots per inch.
     ///
     /// # Returns
     /// A `Result` indicating success or an `ApplicationError`.
-    fn create_session_directory<S>(&self, path: S, mode: u32, uid: u32, gid: u32) -> Result<(), ApplicationError> where S: AsRef<str> {
-        let path = path.as_ref();
-        mkdir(path)?;
-        // ensure permissions and ownership are correct
-        chown(path, uid, gid)?;
-        chmod(path, mode)?;
+    fn load_dpi_resource(&self, display: &str, authority_file_path: &str, dpi: u32) -> Result<(), ApplicationError> {
+        let mut child = Command::new("xrdb")
+            .arg("-merge")
+            .env_clear()
+            .env("DISPLAY", display)
+            .env("XAUTHORITY", authority_file_path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "Xft.dpi: {}", dpi)?;
+        }
+        if !child.wait()?.success() {
+            return Err(ApplicationError::session(format!("Failed to set the DPI resource of display {}", display)));
+        }
         Ok(())
     }
 
-    /// Creates a user-specific file with the specified permissions and ownership.
+    /// Creates a directory for a session with the specified permissions and ownership.
     ///
     /// # Arguments
-    /// * `path` - The path to the file.
-    /// * `mode` - The permissions for the file.
+    /// * `path` - The path to the directory.
+    /// * `mode` - The permissions for the directory.
     /// * `uid` - The user ID to set as the owner.
     /// * `gid` - The group ID to set as the owner.
     ///
     /// # Returns
     /// A `Result` indicating success or an `ApplicationError`.
-    fn create_user_file<S>(&self, path: S, mode: u32, uid: u32, gid: u32) -> Result<(), ApplicationError> where S: AsRef<str> {
+    fn create_session_directory<S>(&self, path: S, mode: u32, uid: u32, gid: u32) -> Result<(), ApplicationError> where S: AsRef<str> {
         let path = path.as_ref();
-
-        if fs::metadata(path).is_err() {
-            touch(path)?;
-        }
-
+        mkdir(path)?;
         // ensure permissions and ownership are correct
-        chmod(path, mode)?;
-        debug!("Changing ownership of file to {}:{}", uid, gid);
         chown(path, uid, gid)?;
+        chmod(path, mode)?;
         Ok(())
     }
 
-    /// Creates the required directories and files for a user session.
+    /// Creates the session directory of a user, containing the Xauthority files of their sessions.
     ///
     /// # Arguments
     /// * `account` - The user account for the session.
@@ -349,12 +506,6 @@ impl XorgService {
             uid,
             gid,
         )?;
-        self.create_user_file(
-            format!("{}/{}/Xauthority", self.settings.sessions_path(), uid),
-            0o640,
-            uid,
-            gid,
-        )?;
         Ok(())
     }
 
@@ -375,13 +526,659 @@ impl XorgService {
         None
     }
 
-    /// Creates an Xauth token, launches the Xorg server, and starts the window manager for a session.
+    /// Returns whether an existing session is resized to the resolution requested when the user logs in again.
+    pub fn resize_on_login(&self) -> bool {
+        self.settings.resize_on_login()
+    }
+
+    /// Changes the screen resolution of a running session and records the new resolution.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    /// * `resolution` - The new screen resolution.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated `Session` or an `ApplicationError` if the session is not found or cannot be resized.
+    pub fn resize(&self, id: &Uuid, resolution: ScreenResolution) -> Result<Session, ApplicationError> {
+        let resolution = self.normalize_resolution(resolution)?;
+        let mut sessions = self.sessions
+            .lock()
+            .map_err(|_| ApplicationError::session("Could not acquire the sessions lock"))?;
+
+        let session = sessions
+            .iter_mut()
+            .find(|session| session.id() == id)
+            .ok_or_else(|| ApplicationError::session(format!("Session {} not found", id)))?;
+
+        let display_server = create_display_server(session.display_server(), &self.settings, None);
+        display_server.resize(session.display_id(), session.server_xauthority_file_path(), &resolution)?;
+        if !resolution.monitors().is_empty() || !session.resolution().monitors().is_empty() {
+            display_server.apply_monitors(session.display_id(), session.server_xauthority_file_path(), resolution.monitors(), session.resolution().monitors())?;
+        }
+
+        info!("Resized session {} from {} to {}", id, session.resolution(), resolution);
+        session.set_resolution(resolution);
+        Ok(session.clone())
+    }
+
+    /// Replaces the cookie given to the clients of a running session. The previous cookie is revoked:
+    /// the clients connected with it are disconnected while the session keeps running.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    ///
+    /// # Returns
+    /// A `Result` containing the updated `Session` or an `ApplicationError` if the session is not found or its cookie cannot be rotated.
+    pub fn rotate_cookie(&self, id: &Uuid) -> Result<Session, ApplicationError> {
+        let mut sessions = self.sessions
+            .lock()
+            .map_err(|_| ApplicationError::session("Could not acquire the sessions lock"))?;
+
+        let session = sessions
+            .iter_mut()
+            .find(|session| session.id() == id)
+            .ok_or_else(|| ApplicationError::session(format!("Session {} not found", id)))?;
+
+        let previous_authorization_id = session.authorization_id().ok_or_else(|| {
+            ApplicationError::session(format!("The cookie of session {} cannot be rotated: its display server does not support the SECURITY extension", id))
+        })?;
+        let (mut client, display) = self.connect_to_display(session)?;
+        let authorization = client.generate(true)?;
+        let cookie = encode_hex(authorization.cookie());
+
+        let gid = fs::metadata(session.xauthority_file_path())?.gid();
+        if let Err(error) = self.create_token(display, session.xauthority_file_path(), &cookie, session.uid(), gid, 0o640) {
+            // do not leave an unused authorization in the display server
+            if let Err(error) = client.revoke(authorization.id()) {
+                warn!("Could not revoke the unused authorization of session {}: {}", id, error);
+            }
+            return Err(error);
+        }
+        // the file holds the new cookie from now on, even if the previous one cannot be revoked
+        session.set_cookie(cookie, Some(authorization.id()));
+
+        if let Err(error) = client.revoke(previous_authorization_id) {
+            audit!("Rotated the cookie of session {} of user {} but could not revoke the previous one: {}", id, session.username(), error);
+            return Err(ApplicationError::session(format!(
+                "The cookie of session {} has been replaced but the previous authorization {} is still active: {}",
+                id, previous_authorization_id, error
+            )));
+        }
+
+        audit!("Rotated the cookie of session {} of user {}", id, session.username());
+        Ok(session.clone())
+    }
+
+    /// Grants another user access to a running session with a cookie of their own, written to a dedicated Xauthority file.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    /// * `grantee` - The username of the user granted access.
+    /// * `view_only` - Whether the grantee may only view the session.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Grant` or an `ApplicationError` if the session is not found or access cannot be granted.
+    pub fn grant(&self, id: &Uuid, grantee: &str, view_only: bool) -> Result<Grant, ApplicationError> {
+        let mut sessions = self.sessions
+            .lock()
+            .map_err(|_| ApplicationError::session("Could not acquire the sessions lock"))?;
+
+        let session = sessions
+            .iter_mut()
+            .find(|session| session.id() == id)
+            .ok_or_else(|| ApplicationError::session(format!("Session {} not found", id)))?;
+
+        if session.username() == grantee {
+            return Err(ApplicationError::session(format!("User {} owns session {}", grantee, id)));
+        }
+        if session.grants().iter().any(|grant| grant.grantee() == grantee) {
+            return Err(ApplicationError::session(format!("User {} already has access to session {}", grantee, id)));
+        }
+        if session.authorization_id().is_none() {
+            return Err(ApplicationError::session(format!("Access to session {} cannot be granted: its display server does not support the SECURITY extension", id)));
+        }
+
+        let (mut client, display) = self.connect_to_display(session)?;
+        let authorization = client.generate(true)?;
+        let cookie = encode_hex(authorization.cookie());
+
+        let file_path = format!("{}/{}.grant.{}.Xauthority", self.settings.sessions_path_for_uid(session.uid()), id.simple(), grantee);
+        let gid = fs::metadata(session.xauthority_file_path())?.gid();
+        if let Err(error) = self.create_token(display, &file_path, &cookie, session.uid(), gid, 0o640) {
+            // do not leave an unused authorization in the display server
+            if let Err(error) = client.revoke(authorization.id()) {
+                warn!("Could not revoke the unused authorization of session {}: {}", id, error);
+            }
+            return Err(error);
+        }
+
+        let grant = Grant::new(grantee.into(), view_only, file_path, cookie, authorization.id(), chrono::Utc::now().timestamp());
+        session.add_grant(grant.clone());
+        audit!(
+            "User {} granted {} access to session {} of user {}",
+            grantee,
+            if view_only { "view-only" } else { "shared" },
+            id,
+            session.username()
+        );
+        Ok(grant)
+    }
+
+    /// Revokes the access to a session granted to a user. The clients connected with the cookie of the grant are disconnected.
+    ///
+    /// # Arguments
+    /// * `id` - The unique identifier of the session.
+    /// * `grantee` - The username of the user granted access.
+    ///
+    /// # Returns
+    /// A `Result` indicating whether the user was granted access, or an `ApplicationError` if the session is not found
+    /// or the cookie cannot be revoked.
+    pub fn revoke_grant(&self, id: &Uuid, grantee: &str) -> Result<bool, ApplicationError> {
+        let mut sessions = self.sessions
+            .lock()
+            .map_err(|_| ApplicationError::session("Could not acquire the sessions lock"))?;
+
+        let session = sessions
+            .iter_mut()
+            .find(|session| session.id() == id)
+            .ok_or_else(|| ApplicationError::session(format!("Session {} not found", id)))?;
+
+        let authorization_id = match session.grants().iter().find(|grant| grant.grantee() == grantee) {
+            Some(grant) => grant.authorization_id(),
+            None => return Ok(false),
+        };
+        let (mut client, _) = self.connect_to_display(session)?;
+        client.revoke(authorization_id)?;
+
+        if let Some(grant) = session.remove_grant(grantee) {
+            self.remove_authority_file(grant.xauthority_file_path());
+        }
+        audit!("Access of user {} to session {} of user {} revoked", grantee, id, session.username());
+        Ok(true)
+    }
+
+    /// Shows a desktop notification in sessions with `notify-send`, run as the user of each session. The commands run
+    /// concurrently and those that have not completed within `NOTIFICATION_TIMEOUT` are killed.
+    ///
+    /// # Arguments
+    /// * `sessions` - The sessions in which the notification is shown.
+    /// * `notification` - The notification.
+    ///
+    /// # Returns
+    /// The result of the notification of each session, in the order of the sessions.
+    pub fn notify(&self, sessions: &[Session], notification: &Notification) -> Vec<Result<(), ApplicationError>> {
+        let children: Vec<Result<Child, ApplicationError>> = sessions
+            .iter()
+            .map(|session| self.spawn_notification(session, notification))
+            .collect();
+
+        let deadline = time::Instant::now() + NOTIFICATION_TIMEOUT;
+        children
+            .into_iter()
+            .map(|child| child.and_then(|child| wait_for_notification(child, deadline)))
+            .collect()
+    }
+
+    /// Retrieves the desktop environments offered to the users: the entries of the xsessions directory,
+    /// restricted to the configured desktops.
+    ///
+    /// # Returns
+    /// A vector of `Desktop` instances sorted by id.
+    pub fn get_desktops(&self) -> Vec<Desktop> {
+        let entries = match fs::read_dir(self.settings.xsessions_path()) {
+            Ok(entries) => entries,
+            Err(error) => {
+                warn!("Could not read the xsessions directory {}: {}", self.settings.xsessions_path(), error);
+                return vec![];
+            }
+        };
+
+        let mut desktops: Vec<Desktop> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "desktop"))
+            .filter_map(|path| Desktop::from_file(&path))
+            .filter(|desktop| self.settings.desktops().is_none_or(|desktops| desktops.iter().any(|id| id == desktop.id())))
+            .collect();
+        desktops.sort_by(|a, b| a.id().cmp(b.id()));
+        desktops
+    }
+
+    /// Retrieves an offered desktop environment by its id.
+    ///
+    /// # Arguments
+    /// * `id` - The id of the desktop.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Desktop` or an `ApplicationError` if it is not offered.
+    fn get_desktop(&self, id: &str) -> Result<Desktop, ApplicationError> {
+        self.get_desktops()
+            .into_iter()
+            .find(|desktop| desktop.id() == id)
+            .ok_or_else(|| ApplicationError::session(format!("The desktop {} is not available", id)))
+    }
+
+    /// Converts the resource limits of a profile into the limits to set on the processes of a session.
+    ///
+    /// # Arguments
+    /// * `limits` - The resource limit settings.
+    ///
+    /// # Returns
+    /// A vector of resources and their limits.
+    fn get_resource_limits(&self, limits: &ResourceLimitSettings) -> Vec<(Resource, u64)> {
+        vec![
+            (Resource::RLIMIT_AS, limits.memory().map(|memory| memory.saturating_mul(1024 * 1024))),
+            (Resource::RLIMIT_NPROC, limits.processes()),
+            (Resource::RLIMIT_NOFILE, limits.open_files()),
+            (Resource::RLIMIT_CPU, limits.cpu_time()),
+        ]
+            .into_iter()
+            .filter_map(|(resource, limit)| limit.map(|limit| (resource, limit)))
+            .collect()
+    }
+
+    /// Determines the DPI and color depth of a session: those requested at login or the defaults of the settings.
+    ///
+    /// # Arguments
+    /// * `options` - The options chosen at login.
+    ///
+    /// # Returns
+    /// A `Result` containing the `DisplayProperties` or an `ApplicationError` if the requested values are not supported.
+    fn get_display_properties(&self, options: &SessionOptions) -> Result<DisplayProperties, ApplicationError> {
+        let properties = DisplayProperties::new(
+            options.dpi().unwrap_or_else(|| self.settings.dpi()),
+            options.depth().unwrap_or_else(|| self.settings.depth()),
+        );
+        if !properties.is_supported() {
+            return Err(ApplicationError::session(format!(
+                "Unsupported display properties {}: the DPI must be between {} and {} and the color depth among {:?}",
+                properties,
+                DisplayProperties::MIN_DPI,
+                DisplayProperties::MAX_DPI,
+                DisplayProperties::DEPTHS
+            )));
+        }
+        Ok(properties)
+    }
+
+    /// Validates the labels provided by a client: their number and the length of their keys and values are limited.
+    ///
+    /// # Arguments
+    /// * `labels` - The labels provided by the client.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError` if the labels exceed the limits.
+    fn validate_labels(&self, labels: &HashMap<String, String>) -> Result<(), ApplicationError> {
+        if labels.len() > MAX_LABELS {
+            return Err(ApplicationError::session(format!("A session cannot have more than {} labels", MAX_LABELS)));
+        }
+        if let Some((key, _)) = labels.iter().find(|(key, value)| key.is_empty() || key.len() > MAX_LABEL_LENGTH || value.len() > MAX_LABEL_LENGTH) {
+            return Err(ApplicationError::session(format!(
+                "Invalid label {:.32}: keys must not be empty and keys and values cannot exceed {} bytes",
+                key, MAX_LABEL_LENGTH
+            )));
+        }
+        Ok(())
+    }
+
+    /// Filters the environment variables provided by a client, keeping those permitted by the settings.
+    /// The variables managed by the session manager and those of the dynamic loader are never accepted.
+    ///
+    /// # Arguments
+    /// * `environment` - The environment variables provided by the client.
+    ///
+    /// # Returns
+    /// A vector of the permitted environment variable names and values, sorted by name.
+    fn get_client_environment(&self, environment: &HashMap<String, String>) -> Vec<(String, String)> {
+        let patterns = self.settings.client_environment();
+        let mut permitted: Vec<(String, String)> = environment
+            .iter()
+            .filter(|(name, value)| {
+                let valid = !name.is_empty()
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    && !value.contains('\0');
+                let reserved = RESERVED_ENVIRONMENT_PREFIXES.iter().any(|prefix| name.starts_with(prefix)) || RESERVED_ENVIRONMENT.contains(&name.as_str());
+                let allowed = patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => name.starts_with(prefix),
+                    None => pattern == *name,
+                });
+                if !valid || reserved || !allowed {
+                    warn!("Ignoring the environment variable {} provided by the client", name);
+                    return false;
+                }
+                true
+            })
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        permitted.sort();
+        permitted
+    }
+
+    /// Verifies that a requested locale is available on the host (as listed by `locale -a`).
+    ///
+    /// # Arguments
+    /// * `locale` - The requested locale, for example `fr_CH.UTF-8`.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError` if the locale is invalid or not available.
+    fn validate_locale(&self, locale: &str) -> Result<(), ApplicationError> {
+        if locale.is_empty() || !locale.chars().all(|c| c.is_ascii_alphanumeric() || "_.@-".contains(c)) {
+            return Err(ApplicationError::session(format!("Invalid locale {}", locale)));
+        }
+
+        // locale -a lists the codesets without the dash (en_US.utf8)
+        let normalize = |locale: &str| locale.to_lowercase().replace("utf-8", "utf8");
+        let output = Command::new("locale").arg("-a").env_clear().output()?;
+        let available = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .any(|available| normalize(available.trim()) == normalize(locale));
+        if !available {
+            return Err(ApplicationError::session(format!("The locale {} is not available", locale)));
+        }
+        Ok(())
+    }
+
+    /// Verifies that a requested timezone exists in the zoneinfo directory of the host.
+    ///
+    /// # Arguments
+    /// * `timezone` - The requested timezone, for example `Europe/Paris`.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError` if the timezone is invalid or unknown.
+    fn validate_timezone(&self, timezone: &str) -> Result<(), ApplicationError> {
+        let valid = !timezone.is_empty()
+            && !timezone.starts_with('/')
+            && !timezone.split('/').any(|part| part.is_empty() || part == "." || part == "..")
+            && timezone.chars().all(|c| c.is_ascii_alphanumeric() || "_+-/".contains(c));
+        if !valid {
+            return Err(ApplicationError::session(format!("Invalid timezone {}", timezone)));
+        }
+        if !Path::new(self.settings.zoneinfo_path()).join(timezone).is_file() {
+            return Err(ApplicationError::session(format!("Unknown timezone {}", timezone)));
+        }
+        Ok(())
+    }
+
+    /// Verifies that the model, layouts and variants of a requested keyboard are listed in the XKB rules of the host.
+    /// Only the names are checked if the rules cannot be read.
+    ///
+    /// # Arguments
+    /// * `keyboard` - The requested keyboard.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError` if the keyboard is invalid or unknown.
+    fn validate_keyboard(&self, keyboard: &KeyboardLayout) -> Result<(), ApplicationError> {
+        let fields = [("model", keyboard.model()), ("layout", Some(keyboard.layout())), ("variant", keyboard.variant())];
+        for (section, value) in fields.iter() {
+            if let Some(value) = value {
+                if value.is_empty() || !value.chars().all(|c| c.is_ascii_alphanumeric() || "_-,".contains(c)) {
+                    return Err(ApplicationError::session(format!("Invalid keyboard {} {}", section, value)));
+                }
+            }
+        }
+
+        let rules = match fs::read_to_string(self.settings.xkb_rules_path()) {
+            Ok(rules) => rules,
+            Err(error) => {
+                debug!("Could not read the XKB rules {}: {}", self.settings.xkb_rules_path(), error);
+                return Ok(());
+            }
+        };
+
+        // the rules list the names of each section ("! model", "! layout", "! variant") at the start of their lines
+        let mut names: HashMap<&str, HashSet<&str>> = HashMap::new();
+        let mut section = "";
+        for line in rules.lines() {
+            if let Some(header) = line.strip_prefix('!') {
+                section = header.trim();
+            } else if let Some(name) = line.split_whitespace().next() {
+                names.entry(section).or_default().insert(name);
+            }
+        }
+
+        for (section, value) in fields.iter() {
+            if let Some(value) = value {
+                let known = names.get(section);
+                if let Some(name) = value.split(',').find(|name| !name.is_empty() && !known.is_some_and(|known| known.contains(name))) {
+                    return Err(ApplicationError::session(format!("Unknown keyboard {} {}", section, name)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Spawns `notify-send` as the user of a session, with the display, the server Xauthority file and the runtime
+    /// directory of the session. The session bus is found at the standard path of the runtime directory if it exists,
+    /// otherwise through the display, on which `dbus-launch` registers the bus it starts.
+    ///
+    /// # Arguments
+    /// * `session` - The session in which the notification is shown.
+    /// * `notification` - The notification.
+    ///
+    /// # Returns
+    /// A `Result` containing the `notify-send` process or an `ApplicationError`.
+    fn spawn_notification(&self, session: &Session, notification: &Notification) -> Result<Child, ApplicationError> {
+        let account = User::from_uid(Uid::from_raw(session.uid()))
+            .ok()
+            .flatten()
+            .and_then(Account::from_user)
+            .ok_or_else(|| ApplicationError::session(format!("Could not find the user of session {}", session.id())))?;
+
+        let xdg_run_time_dir = self.settings.sessions_path_for_uid(account.uid());
+        let mut command = Command::new("notify-send");
+        command
+            .arg("--app-name")
+            .arg("WebX")
+            .arg("--urgency")
+            .arg(notification.urgency().to_string())
+            .arg("--")
+            .arg(notification.summary());
+        if let Some(body) = notification.body() {
+            command.arg(body);
+        }
+
+        command
+            .env_clear()
+            .env("DISPLAY", session.display_id())
+            .env("XAUTHORITY", session.server_xauthority_file_path())
+            .env("HOME", account.home())
+            .env("XDG_RUNTIME_DIR", &xdg_run_time_dir)
+            .current_dir(account.home())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+
+        let bus_path = format!("{}/bus", xdg_run_time_dir);
+        if Path::new(&bus_path).exists() {
+            command.env("DBUS_SESSION_BUS_ADDRESS", format!("unix:path={}", bus_path));
+        }
+
+        let gids: Vec<Gid> = account.groups().iter().map(|&g| Gid::from_raw(g)).collect();
+        let uid = Uid::from_raw(account.uid());
+        let gid = Gid::from_raw(account.gid());
+        unsafe {
+            // run as the user of the session so that the notification is sent on their session bus
+            command
+                .pre_exec(move || {
+                    setgroups(&gids)?;
+                    setgid(gid)?;
+                    setuid(uid)?;
+
+                    Ok(())
+                });
+        }
+
+        command
+            .spawn()
+            .map_err(|error| ApplicationError::session(format!("Failed to spawn notify-send for session {}: {}", session.id(), error)))
+    }
+
+    /// Applies the keyboard of a session to its display with `setxkbmap`.
+    ///
+    /// # Arguments
+    /// * `display` - The X11 display.
+    /// * `authority_file_path` - The path to the Xauthority file of the session.
+    /// * `keyboard` - The keyboard of the session.
+    ///
+    /// # Returns
+    /// A `Result` indicating success or an `ApplicationError`.
+    fn set_keyboard(&self, display: &str, authority_file_path: &str, keyboard: &KeyboardLayout) -> Result<(), ApplicationError> {
+        let mut command = Command::new("setxkbmap");
+        if let Some(model) = keyboard.model() {
+            command.arg("-model").arg(model);
+        }
+        command.arg("-layout").arg(keyboard.layout());
+        if let Some(variant) = keyboard.variant() {
+            command.arg("-variant").arg(variant);
+        }
+
+        let output = command
+            .env_clear()
+            .env("DISPLAY", display)
+            .env("XAUTHORITY", authority_file_path)
+            .output()?;
+        if !output.status.success() {
+            return Err(ApplicationError::session(format!(
+                "Failed to set the keyboard of display {} to {}: {}",
+                display,
+                keyboard,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Verifies that a requested screen resolution (and its monitor layout) is acceptable and snaps the resolution
+    /// of single-monitor screens to the configured multiple and modes.
+    ///
+    /// # Arguments
+    /// * `resolution` - The requested screen resolution.
+    ///
+    /// # Returns
+    /// A `Result` containing the `ScreenResolution` to apply or an `ApplicationError` describing why the resolution is rejected.
+    fn normalize_resolution(&self, resolution: ScreenResolution) -> Result<ScreenResolution, ApplicationError> {
+        let limits = self.settings.resolution().clone().unwrap_or_default();
+        let monitors = resolution.monitors();
+
+        let resolution = if monitors.is_empty() {
+            let multiple = limits.multiple();
+            let (width, height) = resolution.split();
+            let width = (width.saturating_add(multiple / 2) / multiple) * multiple;
+            let height = (height.saturating_add(multiple / 2) / multiple) * multiple;
+
+            let modes = limits.modes();
+            if modes.is_empty() {
+                ScreenResolution::new(width, height)
+            } else {
+                // use the largest allowed mode fitting within the requested resolution
+                modes
+                    .into_iter()
+                    .filter(|mode| mode.width() <= width && mode.height() <= height)
+                    .max_by_key(|mode| mode.width() as u64 * mode.height() as u64)
+                    .ok_or_else(|| ApplicationError::invalid_resolution(format!("No allowed mode fits within the resolution {}x{}", width, height)))?
+            }
+        } else {
+            let max_monitors = self.settings.max_monitors();
+            if monitors.len() > max_monitors {
+                return Err(ApplicationError::invalid_resolution(format!("The monitor layout has {} monitors, the maximum is {}", monitors.len(), max_monitors)));
+            }
+            if let Some(monitor) = monitors.iter().find(|monitor| monitor.width() == 0 || monitor.height() == 0) {
+                return Err(ApplicationError::invalid_resolution(format!("The monitor {} has no area", monitor)));
+            }
+            if monitors.iter().filter(|monitor| monitor.is_primary()).count() > 1 {
+                return Err(ApplicationError::invalid_resolution("The monitor layout has more than one primary monitor"));
+            }
+            if monitors.iter().all(|monitor| monitor.x() > 0) || monitors.iter().all(|monitor| monitor.y() > 0) {
+                return Err(ApplicationError::invalid_resolution("The monitor layout must start at the top left corner of the screen"));
+            }
+            resolution
+        };
+
+        let (width, height) = resolution.split();
+        if width < limits.min_width() || height < limits.min_height() {
+            return Err(ApplicationError::invalid_resolution(format!("The resolution {} is below the minimum {}x{}", resolution, limits.min_width(), limits.min_height())));
+        }
+        if width > limits.max_width() || height > limits.max_height() {
+            return Err(ApplicationError::invalid_resolution(format!("The resolution {} exceeds the maximum {}x{}", resolution, limits.max_width(), limits.max_height())));
+        }
+        if width as u64 * height as u64 > limits.max_pixels() {
+            return Err(ApplicationError::invalid_resolution(format!("The resolution {} exceeds the maximum of {} pixels", resolution, limits.max_pixels())));
+        }
+        Ok(resolution)
+    }
+
+    /// Retrieves the profile applied when none is chosen at login, if the account may use it.
+    ///
+    /// # Arguments
+    /// * `account` - The user account for the session.
+    ///
+    /// # Returns
+    /// An `Option` containing the default `ProfileSettings`.
+    fn get_default_profile(&self, account: &Account) -> Option<&ProfileSettings> {
+        let name = self.default_profile.as_deref()?;
+        match self.get_profile(name, account) {
+            Ok(profile) => Some(profile),
+            Err(error) => {
+                debug!("Not applying the default profile: {}", error);
+                None
+            }
+        }
+    }
+
+    /// Retrieves a session profile by its name, verifying the account may use it.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the profile.
+    /// * `account` - The user account for the session.
+    ///
+    /// # Returns
+    /// A `Result` containing the `ProfileSettings` or an `ApplicationError` if the profile is unknown or not permitted.
+    fn get_profile(&self, name: &str, account: &Account) -> Result<&ProfileSettings, ApplicationError> {
+        let profile = self.profiles
+            .iter()
+            .find(|profile| profile.name() == name)
+            .ok_or_else(|| ApplicationError::session(format!("Unknown session profile {}", name)))?;
+
+        let groups = profile.groups();
+        if !groups.is_empty() && !groups.iter().any(|group| account.is_member_of(group)) {
+            return Err(ApplicationError::session(format!("User {} is not permitted to use the session profile {}", account.username(), name)));
+        }
+        Ok(profile)
+    }
+
+    /// Determines the display server backend for a session: the requested one if it is permitted,
+    /// otherwise the one of the profile or the default.
+    ///
+    /// # Arguments
+    /// * `requested` - The name of the display server requested at login, if any.
+    /// * `profile` - The session profile, if any.
+    ///
+    /// # Returns
+    /// A `Result` containing the `DisplayServer` or an `ApplicationError` if the requested one is unknown or not permitted.
+    fn get_display_server(&self, requested: Option<&str>, profile: Option<&ProfileSettings>) -> Result<Box<dyn DisplayServer>, ApplicationError> {
+        let profile_display_server = profile.and_then(|profile| profile.display_server());
+        let kind = match requested {
+            Some(requested) => {
+                let kind = DisplayServerKind::from_str(requested)?;
+                if !self.settings.display_servers().contains(&kind) && profile_display_server != Some(kind) {
+                    return Err(ApplicationError::session(format!("The display server {} is not permitted", kind)));
+                }
+                kind
+            }
+            None => profile_display_server.unwrap_or_else(|| self.settings.display_server()),
+        };
+        let config_path = profile.and_then(|profile| profile.config_path());
+        Ok(create_display_server(kind, &self.settings, config_path))
+    }
+
+    /// Creates an Xauth token, launches the display server, and starts the window manager for a session.
     ///
     /// # Arguments
     /// * `account` - The user account for the session.
     /// * `webx_user` - The WebX system user.
     /// * `resolution` - The screen resolution for the session.
     /// * `environment` - The environment variables for the session.
+    /// * `options` - The options chosen at login, such as the display server and the desktop.
     ///
     /// # Returns
     /// A `Result` containing the created `Session` or an `ApplicationError`.
@@ -391,52 +1188,157 @@ impl XorgService {
         webx_user: &User,
         resolution: ScreenResolution,
         environment: EnvList,
+        options: &SessionOptions,
     ) -> Result<Session, ApplicationError> {
-        let display_id = self.get_next_display()?;
+        let profile = match options.profile() {
+            Some(name) => Some(self.get_profile(name, account)?),
+            None => self.get_default_profile(account),
+        };
+        let display_server = self.get_display_server(options.display_server(), profile)?;
+        let desktop = options.desktop().map(|id| self.get_desktop(id)).transpose()?;
+        if desktop.is_some() && profile.is_some_and(|profile| profile.application().is_some()) {
+            return Err(ApplicationError::session("A desktop cannot be chosen with a kiosk profile"));
+        }
+        let resolution = self.normalize_resolution(resolution)?;
+        let properties = self.get_display_properties(options)?;
+        self.validate_labels(options.labels())?;
+        if let Some(keyboard) = options.keyboard() {
+            self.validate_keyboard(keyboard)?;
+        }
 
-        self.create_token(display_id, account, webx_user)?;
+        // the permitted variables of the client, the locale and the timezone are exported to the session
+        let mut session_environment = self.get_client_environment(options.environment());
+        if let Some(locale) = options.locale() {
+            self.validate_locale(locale)?;
+            session_environment.push(("LANG".into(), locale.into()));
+            session_environment.push(("WEBX_LOCALE".into(), locale.into()));
+        }
+        if let Some(timezone) = options.timezone() {
+            self.validate_timezone(timezone)?;
+            session_environment.push(("TZ".into(), timezone.into()));
+        }
+        let display_id = self.get_next_display()?;
 
         let session_id = Uuid::new_v4();
 
+        // the display server and the window manager use a cookie of their own, the clients a revocable one
+        let (authority_file_path, client_authority_file_path) = self.get_authority_file_paths(&session_id, account.uid());
+        let webx_gid = webx_user.gid.as_raw();
+        let server_cookie = self.create_cookie();
+        self.create_token(display_id, &authority_file_path, &server_cookie, account.uid(), webx_gid, 0o600)?;
+
         // spawn the x server
-        let xorg = self.spawn_x_server(&session_id, display_id, &resolution, account, &environment)?;
+        let xorg = match self.spawn_x_server(&session_id, display_server.as_ref(), display_id, &authority_file_path, &resolution, &properties, account, &environment, &session_environment) {
+            Ok(xorg) => xorg,
+            Err(error) => {
+                self.remove_authority_file(&authority_file_path);
+                return Err(error);
+            }
+        };
+
+        // Sleep for 1 second or the startup wait of the profile (wait for x server to start)
+        let startup_wait = profile.map_or(1000, |profile| profile.startup_wait());
+        thread::sleep(time::Duration::from_millis(startup_wait));
+
+        let (cookie, authorization_id) = self.create_client_cookie(display_id, &server_cookie);
+        if let Err(error) = self.create_token(display_id, &client_authority_file_path, &cookie, account.uid(), webx_gid, 0o640) {
+            self.abort_execute(&xorg, &authority_file_path, &client_authority_file_path);
+            return Err(error);
+        }
+
+        // apply the resolution for display servers that cannot be started with it
+        if let Err(error) = display_server.apply_resolution(&format!(":{}", display_id), &authority_file_path, &resolution) {
+            warn!("{}", error);
+        }
 
-        // Sleep for 1 second (wait for x server to start)
-        thread::sleep(time::Duration::from_millis(1000));
+        // set the DPI used by the desktop toolkits for their fonts
+        if properties.dpi() != 96 {
+            if let Err(error) = self.load_dpi_resource(&format!(":{}", display_id), &authority_file_path, properties.dpi()) {
+                warn!("{}", error);
+            }
+        }
+
+        // apply the keyboard requested by the client
+        if let Some(keyboard) = options.keyboard() {
+            if let Err(error) = self.set_keyboard(&format!(":{}", display_id), &authority_file_path, keyboard) {
+                warn!("{}", error);
+            }
+        }
+
+        // define the monitors of a multi-monitor layout
+        if !resolution.monitors().is_empty() {
+            if let Err(error) = display_server.apply_monitors(&format!(":{}", display_id), &authority_file_path, resolution.monitors(), &[]) {
+                warn!("{}", error);
+            }
+        }
 
         // spawn the window manager
-        let window_manager = self.spawn_window_manager(&session_id, display_id, account, &environment)?;
+        let window_manager = match self.spawn_window_manager(&session_id, display_id, &authority_file_path, account, &environment, &session_environment, desktop.as_ref(), profile, &properties) {
+            Ok(window_manager) => window_manager,
+            Err(error) => {
+                self.abort_execute(&xorg, &authority_file_path, &client_authority_file_path);
+                return Err(error);
+            }
+        };
 
         info!(
-            "Running xorg display {} on process id {} with window manager process id {}",
+            "Running {} display {} on process id {} with window manager process id {}",
+            display_server.kind(),
             display_id,
             xorg.pid(),
             window_manager.pid()
         );
 
-        let authority_file_path = format!(
-            "{}/{}/Xauthority",
-            self.settings.sessions_path(),
-            account.uid()
-        );
-
-        let session = Session::new(
+        let mut session = Session::new(
             session_id,
             account.username().into(),
             account.uid(),
             format!(":{}", display_id),
-            authority_file_path,
+            client_authority_file_path,
             xorg,
             window_manager,
             resolution,
-        );
+            display_server.kind(),
+        )
+            .with_server_authority(authority_file_path, server_cookie)
+            .with_display_properties(properties)
+            .with_labels(options.labels().clone());
+        session.set_cookie(cookie, authorization_id);
+        let session = match profile {
+            Some(profile) => {
+                let end_on_exit = profile.application().is_some() && profile.on_exit() == ProfileExitPolicy::Logout;
+                let session = session.with_profile(profile.name(), end_on_exit);
+                match profile.max_duration() {
+                    Some(max_duration) => session.with_expiry(chrono::Utc::now().timestamp() + max_duration as i64 * 60, profile.max_duration_warning()),
+                    None => session,
+                }
+            }
+            None => session,
+        };
         if let Ok(mut sessions) = self.sessions.lock() {
             sessions.push(session.clone());
             return Ok(session);
         }
+        session.window_manager().kill()?;
+        self.abort_execute(session.xorg(), session.server_xauthority_file_path(), session.xauthority_file_path());
         return Err(ApplicationError::session(format!("Could not start session for user: {}", account)));
     }
 
+    /// Releases the resources of a session that could not be started: no `Session` exists yet, so they would
+    /// otherwise not be reclaimed by the clean up procedure.
+    ///
+    /// # Arguments
+    /// * `xorg` - The display server process of the session.
+    /// * `authority_file_path` - The path to the Xauthority file of the display server.
+    /// * `client_authority_file_path` - The path to the Xauthority file of the clients.
+    fn abort_execute(&self, xorg: &ProcessHandle, authority_file_path: &str, client_authority_file_path: &str) {
+        if let Err(error) = xorg.kill() {
+            error!("Could not kill the display server of a session that could not be started: {}", error);
+        }
+        self.remove_authority_file(authority_file_path);
+        self.remove_authority_file(client_authority_file_path);
+    }
+
     /// Finds the next available display number for a session.
     ///
     /// # Arguments
@@ -463,3 +1365,58 @@ impl XorgService {
         self.get_next_available_display(display_offset)
     }
 }
+
+/// Waits for a `notify-send` process to complete, killing it if it has not completed by the deadline. Its error
+/// output is read in a separate thread while waiting so that the process never blocks on a full pipe.
+///
+/// # Arguments
+/// * `child` - The `notify-send` process.
+/// * `deadline` - The instant at which the process is killed.
+///
+/// # Returns
+/// A `Result` indicating the notification has been sent or an `ApplicationError`.
+fn wait_for_notification(mut child: Child, deadline: time::Instant) -> Result<(), ApplicationError> {
+    let (sender, receiver) = mpsc::channel();
+    if let Some(mut stderr) = child.stderr.take() {
+        thread::spawn(move || {
+            let mut output = Vec::new();
+            let _ = stderr.read_to_end(&mut output);
+            let _ = sender.send(output);
+        });
+    }
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if time::Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(ApplicationError::session("notify-send did not complete in time"));
+        }
+        thread::sleep(time::Duration::from_millis(50));
+    };
+
+    if !status.success() {
+        // the pipe may be held open by a descendant of the process, so the error output is not waited for past the deadline
+        let stderr = receiver
+            .recv_timeout(deadline.saturating_duration_since(time::Instant::now()))
+            .unwrap_or_default();
+        return Err(ApplicationError::session(format!("notify-send failed ({}): {}", status, String::from_utf8_lossy(&stderr).trim())));
+    }
+    Ok(())
+}
+
+/// Encodes bytes as a lowercase hexadecimal string, the format of the cookies of the sessions.
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decodes a hexadecimal string into bytes, skipping the pairs that are not hexadecimal.
+fn decode_hex(value: &str) -> Vec<u8> {
+    value
+        .as_bytes()
+        .chunks_exact(2)
+        .filter_map(|pair| std::str::from_utf8(pair).ok().and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}